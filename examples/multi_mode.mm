@@ -0,0 +1,71 @@
+************************************************************************
+file with basedata            : mm_example.bas
+initial value random generator: 28123
+************************************************************************
+projects                      :  1
+jobs (incl. supersource/sink ):  12
+horizon                       :  77
+RESOURCES
+  - renewable                 :  2   R
+  - nonrenewable              :  2   N
+  - doubly constrained        :  0   D
+************************************************************************
+PROJECT INFORMATION:
+pronr.  #jobs rel.date duedate tardcost  MPM-Time
+    1     10      0       15        6       15
+************************************************************************
+PRECEDENCE RELATIONS:
+jobnr.    #modes  #successors   successors
+   1        1          3           2   3   4
+   2        3          2           5  10
+   3        3          1           8
+   4        3          1           6
+   5        3          2           7   9
+   6        3          2           7   8
+   7        3          1          11
+   8        3          1           9
+   9        3          1          12
+  10        3          1          11
+  11        3          1          12
+  12        1          0        
+************************************************************************
+REQUESTS/DURATIONS:
+jobnr. mode duration  R 1  R 2  N 1  N 2
+------------------------------------------------------------------------
+  1      1     0       0    0    0    0
+  2      1     4       2    0    0    2
+         2     5       1    9    1    0
+         3     6       9    0    0    2
+  3      1     4       1    0    0    3
+         2     6       5    2    7    0
+         3     9       0    4    0    5
+  4      1     4       0    5    0    9
+         2     5       7    5    8    0
+         3     6       4    0    0    2
+  5      1     3       5    0    0    4
+         2     4       0    8    3    0
+         3     6       7    0    0    6
+  6      1     2       4    0    0    6
+         2     3       5    0    6    0
+         3     6       2    0    0    6
+  7      1     5       3    2    0   10
+         2     6       8    3    7    0
+         3     9       3    1    0    1
+  8      1     5       6    0    0    8
+         2     6       0    0    1    0
+         3     7       0    1    0   10
+  9      1     4       0    9    0    7
+         2     7       4    0    8    0
+         3     9       0    4    0    8
+ 10      1     5       0    0    0    1
+         2     6       5    0    4    0
+         3     8       0    0    0    6
+ 11      1     4       0    8    0    2
+         2     6       0    6    8    0
+         3     9       6    0    0    5
+ 12      1     0       0    0    0    0
+************************************************************************
+RESOURCEAVAILABILITIES:
+  R 1  R 2  N 1  N 2
+    9    9   45   42
+************************************************************************
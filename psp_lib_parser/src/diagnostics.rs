@@ -0,0 +1,219 @@
+//! Human-readable parse error reports pointing at the offending position of the input
+
+use std::{fmt, ops::Range};
+
+use chumsky::error::{Simple, SimpleReason};
+
+/// Headings introducing the sections of a PSPLIB file, in the order they are matched
+const SECTIONS: [(&str, &str); 7] = [
+    ("file with basedata", "FILE METADATA"),
+    ("projects", "METADATA"),
+    ("RESOURCEAVAILABILITIES", "RESOURCEAVAILABILITIES"),
+    ("RESOURCES", "RESOURCES"),
+    ("PROJECT INFORMATION", "PROJECT INFORMATION"),
+    ("PRECEDENCE RELATIONS", "PRECEDENCE RELATIONS"),
+    ("REQUESTS/DURATIONS", "REQUESTS/DURATIONS"),
+];
+
+/// A single parse error with its location in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDiagnostic {
+    /// Character range of the offending input
+    pub span: Range<usize>,
+    /// 1-based line of the span start
+    pub line: usize,
+    /// 1-based column of the span start
+    pub column: usize,
+    /// PSPLIB section the error occurred in, if any
+    pub section: Option<&'static str>,
+    /// Label of the innermost parser that failed
+    pub label: Option<&'static str>,
+    /// Descriptions of the tokens that would have been accepted, sorted and deduplicated
+    pub expected: Vec<String>,
+    /// Description of the token at the span, e.g. `'v'`, `newline` or `end of input`
+    pub found: Option<String>,
+    /// Explanation for errors that are not a token mismatch
+    pub reason: Option<String>,
+    /// The complete input line the span starts in
+    pub source_line: String,
+}
+
+/// All parse errors of an input, rendered one after another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDiagnostics(pub Vec<ParseDiagnostic>);
+
+impl ParseDiagnostic {
+    pub fn new(content: &str, error: &Simple<char>) -> Self {
+        let span = error.span();
+
+        let mut line = 1;
+        let mut line_start = 0;
+        let mut section = None;
+        for (index, character) in content.chars().enumerate().take(span.start) {
+            if character == '\n' {
+                line += 1;
+                line_start = index + 1;
+            }
+        }
+
+        let source_lines: Vec<&str> = content.lines().collect();
+        for source_line in source_lines.iter().take(line) {
+            if let Some((_, name)) = SECTIONS
+                .iter()
+                .find(|(heading, _)| source_line.trim_start().starts_with(heading))
+            {
+                section = Some(*name);
+            }
+        }
+
+        let mut expected: Vec<String> = error.expected().map(describe_token).collect();
+        expected.sort();
+        expected.dedup();
+
+        let reason = match error.reason() {
+            SimpleReason::Unexpected => None,
+            SimpleReason::Unclosed { delimiter, .. } => {
+                Some(format!("unclosed delimiter {delimiter:?}"))
+            }
+            SimpleReason::Custom(message) => Some(message.clone()),
+        };
+
+        Self {
+            line,
+            column: span.start - line_start + 1,
+            section,
+            label: error.label(),
+            expected,
+            found: Some(describe_token(&error.found().copied())),
+            reason,
+            source_line: source_lines
+                .get(line - 1)
+                .map(|source_line| source_line.trim_end_matches('\r').to_string())
+                .unwrap_or_default(),
+            span,
+        }
+    }
+}
+
+fn describe_token(token: &Option<char>) -> String {
+    match token {
+        Some('\n') => "newline".to_string(),
+        Some(character) => format!("{character:?}"),
+        None => "end of input".to_string(),
+    }
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error")?;
+        if let Some(section) = self.section {
+            write!(f, " in {section}")?;
+        }
+        write!(f, " at line {}, column {}", self.line, self.column)?;
+        if let Some(label) = self.label {
+            write!(f, " while parsing {label}")?;
+        }
+
+        match &self.reason {
+            Some(reason) => write!(f, ": {reason}")?,
+            None => {
+                if !self.expected.is_empty() {
+                    write!(f, ": expected {}", self.expected.join(", "))?;
+                }
+                if let Some(found) = &self.found {
+                    write!(f, ", found {found}")?;
+                }
+            }
+        }
+        writeln!(f)?;
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        let line_length = self.source_line.chars().count();
+        let marker_start = (self.column - 1).min(line_length);
+        let marker_length = self
+            .span
+            .len()
+            .min(line_length.saturating_sub(marker_start))
+            .max(1);
+
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(marker_start),
+            "^".repeat(marker_length)
+        )
+    }
+}
+
+impl fmt::Display for ParseDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, diagnostic) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{diagnostic}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ParseDiagnostics {
+    pub fn new(content: &str, errors: Vec<Simple<char>>) -> Self {
+        Self(
+            errors
+                .iter()
+                .map(|error| ParseDiagnostic::new(content, error))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_psp_lib, PspLibParseError};
+
+    static TEST_FILE: &str = include_str!("../../examples/j3011_2.sm");
+
+    #[test]
+    fn precedence_heading_diagnostic() {
+        let content = TEST_FILE.replace(
+            "jobnr.    #modes  #successors   successors",
+            "jobnr.    modes  #successors   successors",
+        );
+
+        let Err(PspLibParseError::ParseError(diagnostics)) = parse_psp_lib(&content) else {
+            panic!("expected a parse error");
+        };
+
+        let diagnostic = &diagnostics.0[0];
+        assert_eq!(diagnostic.line, 18);
+        assert_eq!(diagnostic.column, 11);
+        assert_eq!(diagnostic.section, Some("PRECEDENCE RELATIONS"));
+        assert_eq!(diagnostic.found.as_deref(), Some("'m'"));
+
+        let rendered = diagnostics.to_string();
+        assert!(rendered.starts_with("error in PRECEDENCE RELATIONS at line 18, column 11"));
+        assert!(rendered.contains("18 | jobnr.    modes  #successors   successors"));
+        assert!(rendered.ends_with("   |           ^"));
+    }
+
+    #[test]
+    fn out_of_range_diagnostic() {
+        let content = TEST_FILE.replace("   29   31   37   21", " 99999999999 31 37 21");
+
+        let Err(PspLibParseError::ParseError(diagnostics)) = parse_psp_lib(&content) else {
+            panic!("expected a parse error");
+        };
+
+        let diagnostic = &diagnostics.0[0];
+        assert_eq!(diagnostic.section, Some("RESOURCEAVAILABILITIES"));
+        assert_eq!(
+            diagnostic.reason.as_deref(),
+            Some("number 99999999999 is out of range")
+        );
+        assert!(diagnostics.to_string().contains("^^^^^^^^^^^"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Reading instances from disk, transparently inflating gzip-compressed files
+
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use flate2::read::GzDecoder;
+
+use crate::{parse_patterson, parse_psp_lib, parse_rcpsp_max, PspLibParseError, PspLibProblem};
+
+/// Leading bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Decodes the raw contents of an instance file, inflating them first if they are gzip-compressed
+pub fn decode_instance(bytes: &[u8]) -> io::Result<String> {
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return String::from_utf8(bytes.to_vec())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err));
+    }
+
+    let mut contents = String::new();
+    GzDecoder::new(bytes).read_to_string(&mut contents)?;
+
+    Ok(contents)
+}
+
+/// Reads an instance file, which may be gzip-compressed, into a string
+pub fn read_instance(path: impl AsRef<Path>) -> io::Result<String> {
+    decode_instance(&fs::read(path)?)
+}
+
+/// Reads and parses an instance file, selecting the format by its extension.
+///
+/// `.rcp` files are read as Patterson, `.sch` files as RCPSP/max and everything else as
+/// PSPLIB instances. A trailing `.gz` is ignored when selecting the format.
+pub fn parse_file(path: impl AsRef<Path>) -> Result<PspLibProblem, PspLibParseError> {
+    let path = path.as_ref();
+    let contents = read_instance(path)?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .unwrap_or_default();
+    let file_name = file_name.strip_suffix(".gz").unwrap_or(file_name);
+
+    match Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some("rcp") => parse_patterson(&contents),
+        Some("sch") => parse_rcpsp_max(&contents),
+        _ => parse_psp_lib(&contents),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    static TEST_FILE: &str = include_str!("../../examples/j1201_1.sm");
+
+    fn compress(contents: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(contents.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn decode_plain_instance() {
+        assert_eq!(decode_instance(TEST_FILE.as_bytes()).unwrap(), TEST_FILE);
+    }
+
+    #[test]
+    fn decode_gzip_instance() {
+        assert_eq!(decode_instance(&compress(TEST_FILE)).unwrap(), TEST_FILE);
+    }
+
+    #[test]
+    fn parse_gzip_file() {
+        let path =
+            std::env::temp_dir().join(format!("psp_lib_parser_{}.sch.gz", std::process::id()));
+        fs::write(
+            &path,
+            compress(include_str!("../../examples/rcpsp_max_example.sch")),
+        )
+        .unwrap();
+
+        let psp = parse_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        let psp = psp.unwrap();
+        assert_eq!(psp.jobs, 7);
+        assert_eq!(psp.resource_availabilities.resources, vec![3]);
+    }
+}
@@ -1,19 +1,51 @@
 // Either use nom or chumsky to parse text
 // Lets go with chumsky for now
 
+use std::str::FromStr;
+
 use chumsky::{prelude::*, Parser};
-use structs::PspLibProblem;
 use thiserror::Error;
 
+pub mod diagnostics;
+pub mod input;
+pub mod patterson;
+pub mod rcpsp_max;
+pub mod solutions;
 pub mod structs;
+pub mod validation;
+pub mod writer;
+pub use diagnostics::{ParseDiagnostic, ParseDiagnostics};
+pub use input::{decode_instance, parse_file, read_instance};
+pub use patterson::parse_patterson;
+pub use rcpsp_max::parse_rcpsp_max;
+pub use solutions::{
+    decode_solution_list, parse_solution_list, PspLibSolution, PspLibSolutionKey,
+    PspLibSolutionList,
+};
 pub use structs::*;
+pub use validation::{validate_psp_lib, ValidationError};
+pub use writer::{write_psp_lib, PspLibWriteError};
 
 #[derive(Debug, Error)]
 pub enum PspLibParseError {
-    #[error("ParseError occurred")]
-    ParseError(Vec<Simple<char>>),
+    #[error("{0}")]
+    ParseError(ParseDiagnostics),
     #[error("Project info incomplete")]
     ProjectInfoIncomplete,
+    #[error("Precedence relation row {0} has too few columns")]
+    InvalidPrecedenceRelation(usize),
+    #[error("Request/duration row {0} has an unexpected number of columns")]
+    InvalidRequestDuration(usize),
+    #[error("Expected {expected} resources, but found {found} resource columns")]
+    ResourceCountMismatch { expected: usize, found: usize },
+    #[error("Unexpected end of input")]
+    UnexpectedEndOfInput,
+    #[error("Unexpected input after the last activity")]
+    TrailingInput,
+    #[error("Line {0} does not match the expected layout")]
+    InvalidLine(usize),
+    #[error("Failed to read instance: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 pub fn parse_psp_lib(content: &str) -> Result<PspLibProblem, PspLibParseError> {
@@ -40,7 +72,7 @@ pub fn parse_psp_lib(content: &str) -> Result<PspLibProblem, PspLibParseError> {
         resource_availabilities,
     ) = parser
         .parse(content)
-        .map_err(PspLibParseError::ParseError)?;
+        .map_err(|errors| PspLibParseError::ParseError(ParseDiagnostics::new(content, errors)))?;
 
     let project_info: Vec<structs::PspLibProjectInformation> = {
         let mut info = vec![];
@@ -62,12 +94,13 @@ pub fn parse_psp_lib(content: &str) -> Result<PspLibProblem, PspLibParseError> {
     let precedence_relations: Vec<structs::PspLibPrecedenceRelation> = {
         let mut relations = vec![];
 
-        for precedence_relation in precedence_relations {
+        for (row, precedence_relation) in precedence_relations.into_iter().enumerate() {
             let mut iter = precedence_relation.into_iter();
-            // TODO: Return error if not able to unwrap
-            let job_number = iter.next().unwrap_or_default();
-            let mode_count = iter.next().unwrap_or_default();
-            let successor_count = iter.next().unwrap_or_default();
+            let (Some(job_number), Some(mode_count), Some(successor_count)) =
+                (iter.next(), iter.next(), iter.next())
+            else {
+                return Err(PspLibParseError::InvalidPrecedenceRelation(row + 1));
+            };
             let successors = iter.collect();
 
             relations.push(structs::PspLibPrecedenceRelation {
@@ -75,35 +108,66 @@ pub fn parse_psp_lib(content: &str) -> Result<PspLibProblem, PspLibParseError> {
                 mode_count,
                 successor_count,
                 successors,
+                time_lags: vec![],
             });
         }
 
         relations
     };
 
+    let resources = structs::PspLibProblemResources {
+        renewable: metadata[3],
+        nonrenewable: metadata[4],
+        doubly_constrained: metadata[5],
+    };
+    let resource_count = resources.count();
+
+    let (resource_labels, requests_durations) = requests_durations;
+    if resource_labels.len() != resource_count {
+        return Err(PspLibParseError::ResourceCountMismatch {
+            expected: resource_count,
+            found: resource_labels.len(),
+        });
+    }
+
     let request_durations: Vec<structs::PspLibRequestDuration> = {
-        let mut durations = vec![];
+        let mut durations: Vec<structs::PspLibRequestDuration> = vec![];
+
+        for (row, requests_duration) in requests_durations.into_iter().enumerate() {
+            // Follow-up modes of a job omit the job number, thus reuse the previous one
+            let (job_number, mode_columns) = if requests_duration.len() == resource_count + 3 {
+                (requests_duration[0], &requests_duration[1..])
+            } else if requests_duration.len() == resource_count + 2 && !durations.is_empty() {
+                (
+                    durations[durations.len() - 1].job_number,
+                    &requests_duration[..],
+                )
+            } else {
+                return Err(PspLibParseError::InvalidRequestDuration(row + 1));
+            };
 
-        for requests_duration in requests_durations {
             durations.push(structs::PspLibRequestDuration {
-                job_number: requests_duration[0],
-                mode: requests_duration[1],
-                duration: requests_duration[2],
-                r1: requests_duration[3],
-                r2: requests_duration[4],
-                r3: requests_duration[5],
-                r4: requests_duration[6],
+                job_number,
+                mode: mode_columns[0],
+                duration: mode_columns[1],
+                resources: mode_columns[2..].to_vec(),
             });
         }
 
         durations
     };
 
+    let (availability_labels, availabilities) = resource_availabilities;
+    for found in [availability_labels.len(), availabilities.len()] {
+        if found != resource_count {
+            return Err(PspLibParseError::ResourceCountMismatch {
+                expected: resource_count,
+                found,
+            });
+        }
+    }
     let resource_availabilities = structs::PspLibResourceAvailability {
-        r1: resource_availabilities[0],
-        r2: resource_availabilities[1],
-        r3: resource_availabilities[2],
-        r4: resource_availabilities[3],
+        resources: availabilities,
     };
 
     Ok(PspLibProblem {
@@ -112,11 +176,7 @@ pub fn parse_psp_lib(content: &str) -> Result<PspLibProblem, PspLibParseError> {
         projects: metadata[0],
         jobs: metadata[1],
         horizon: metadata[2],
-        resources: structs::PspLibProblemResources {
-            renewable: metadata[3],
-            nonrenewable: metadata[4],
-            doubly_constrained: metadata[5],
-        },
+        resources,
         project_info,
         precedence_relations,
         request_durations,
@@ -124,6 +184,42 @@ pub fn parse_psp_lib(content: &str) -> Result<PspLibProblem, PspLibParseError> {
     })
 }
 
+pub(crate) fn number_parser<T: FromStr>() -> impl Parser<char, T, Error = Simple<char>> {
+    text::int(10).try_map(|number: String, span| {
+        number
+            .parse::<T>()
+            .map_err(|_| Simple::custom(span, format!("number {number} is out of range")))
+    })
+}
+
+/// Spaces and tabs within a line, but never a line break
+pub(crate) fn inline_whitespace_parser() -> impl Parser<char, (), Error = Simple<char>> + Clone {
+    one_of(" \t").repeated().ignored()
+}
+
+/// Matches a heading word by word, allowing any inline whitespace between the words
+pub(crate) fn heading_parser(
+    heading: &'static str,
+) -> impl Parser<char, (), Error = Simple<char>> + Clone {
+    heading
+        .split_whitespace()
+        .map(|word| just(word).ignored().boxed())
+        .reduce(|words, word| {
+            words
+                .then_ignore(inline_whitespace_parser())
+                .then(word)
+                .ignored()
+                .boxed()
+        })
+        .expect("headings consist of at least one word")
+        .labelled(heading)
+}
+
+/// Ends a data row, tolerating trailing whitespace and CRLF line endings
+pub(crate) fn row_end_parser() -> impl Parser<char, (), Error = Simple<char>> + Clone {
+    inline_whitespace_parser().then_ignore(text::newline())
+}
+
 pub(crate) fn separator_parser() -> impl Parser<char, (), Error = Simple<char>> {
     filter(|c: &char| *c == '*' || *c == '-')
         .repeated()
@@ -138,21 +234,21 @@ pub(crate) fn file_metadata_parser() -> impl Parser<char, (String, usize), Error
     let alphanumeric_with_punctuation =
         filter(|c: &char| c.is_ascii_alphanumeric() || c.is_ascii_punctuation()).repeated();
 
-    let basedata = just("file with basedata")
+    // The name may be empty, e.g. for instances converted from other formats, thus only
+    // inline whitespace is skipped in front of it
+    let basedata = heading_parser("file with basedata")
         .padded()
         .then_ignore(just(':'))
-        .padded()
+        .then_ignore(inline_whitespace_parser())
         .ignore_then(alphanumeric_with_punctuation)
         .collect::<String>()
         .labelled("basedata");
 
-    let initial_rng = just("initial value random generator")
+    let initial_rng = heading_parser("initial value random generator")
         .padded()
         .ignore_then(just(':'))
         .padded()
-        .ignore_then(text::int(10))
-        .from_str::<usize>()
-        .unwrapped()
+        .ignore_then(number_parser::<usize>())
         .labelled("initial_rng");
 
     separator
@@ -166,14 +262,16 @@ pub(crate) fn metadata_parser() -> impl Parser<char, Vec<usize>, Error = Simple<
     let separator = separator_parser();
 
     let descriptor = |id| {
-        just(id)
+        heading_parser(id)
             .padded()
             .then_ignore(just(':'))
             .padded()
-            .ignore_then(text::int(10))
-            .then_ignore(just(' ').repeated().then_ignore(text::ident()).or_not())
-            .from_str::<usize>()
-            .unwrapped()
+            .ignore_then(number_parser::<usize>())
+            .then_ignore(
+                inline_whitespace_parser()
+                    .then_ignore(text::ident())
+                    .or_not(),
+            )
             .labelled(id)
     };
 
@@ -192,7 +290,7 @@ pub(crate) fn metadata_parser() -> impl Parser<char, Vec<usize>, Error = Simple<
         .padded()
         .chain(horizon)
         .padded()
-        .then_ignore(just("RESOURCES"))
+        .then_ignore(heading_parser("RESOURCES"))
         .padded()
         .chain(renewable)
         .padded()
@@ -202,96 +300,106 @@ pub(crate) fn metadata_parser() -> impl Parser<char, Vec<usize>, Error = Simple<
         .collect()
 }
 
-pub(crate) fn project_info_parser() -> impl Parser<char, Vec<Vec<u8>>, Error = Simple<char>> {
+pub(crate) fn project_info_parser() -> impl Parser<char, Vec<Vec<u32>>, Error = Simple<char>> {
     let separator = separator_parser();
 
-    let info = text::int(10)
-        .from_str::<u8>()
-        .unwrapped()
-        .then_ignore(just(' ').repeated())
+    let info = number_parser::<u32>()
+        .then_ignore(inline_whitespace_parser())
         .repeated()
         .at_least(6);
 
     separator
-        .then_ignore(just("PROJECT INFORMATION:"))
+        .then_ignore(heading_parser("PROJECT INFORMATION:"))
         .padded()
-        .then_ignore(just("pronr.  #jobs rel.date duedate tardcost  MPM-Time"))
+        .then_ignore(heading_parser(
+            "pronr.  #jobs rel.date duedate tardcost  MPM-Time",
+        ))
         .padded()
         .ignore_then(
             text::whitespace()
                 .ignore_then(info)
-                .then_ignore(text::newline())
+                .then_ignore(row_end_parser())
                 .repeated(),
         )
 }
 
-pub(crate) fn precedence_relation_parser() -> impl Parser<char, Vec<Vec<u8>>, Error = Simple<char>>
+pub(crate) fn precedence_relation_parser() -> impl Parser<char, Vec<Vec<u32>>, Error = Simple<char>>
 {
     let separator = separator_parser();
 
-    let info = text::int(10)
-        .from_str::<u8>()
-        .unwrapped()
-        .then_ignore(just(' ').repeated())
+    let info = number_parser::<u32>()
+        .then_ignore(inline_whitespace_parser())
         .repeated()
         .at_least(3);
 
     separator
-        .then_ignore(just("PRECEDENCE RELATIONS:"))
+        .then_ignore(heading_parser("PRECEDENCE RELATIONS:"))
         .padded()
-        .then_ignore(just("jobnr.    #modes  #successors   successors"))
-        .then_ignore(text::newline())
+        .then_ignore(heading_parser("jobnr.    #modes  #successors   successors"))
+        .then_ignore(row_end_parser())
         .ignore_then(
             text::whitespace()
                 .ignore_then(info)
-                .then_ignore(text::newline())
+                .then_ignore(row_end_parser())
                 .repeated(),
         )
 }
 
-pub(crate) fn requests_duration_parser() -> impl Parser<char, Vec<Vec<u8>>, Error = Simple<char>> {
+pub(crate) fn resource_labels_parser() -> impl Parser<char, Vec<String>, Error = Simple<char>> {
+    one_of("RND")
+        .then_ignore(inline_whitespace_parser())
+        .then(text::int(10))
+        .map(|(kind, number)| format!("{kind} {number}"))
+        .then_ignore(inline_whitespace_parser())
+        .repeated()
+        .at_least(1)
+        .labelled("resource labels")
+}
+
+pub(crate) fn requests_duration_parser(
+) -> impl Parser<char, (Vec<String>, Vec<Vec<u32>>), Error = Simple<char>> {
     let separator = separator_parser();
 
-    let info = text::int(10)
-        .from_str::<u8>()
-        .unwrapped()
-        .then_ignore(just(' ').repeated())
+    // Rows of multi-mode instances only carry a job number on a job's first mode
+    let info = number_parser::<u32>()
+        .then_ignore(inline_whitespace_parser())
         .repeated()
-        .at_least(7);
+        .at_least(3);
 
     separator
-        .then_ignore(just("REQUESTS/DURATIONS:"))
+        .then_ignore(heading_parser("REQUESTS/DURATIONS:"))
         .padded()
-        .then_ignore(just("jobnr. mode duration  R 1  R 2  R 3  R 4"))
+        .then_ignore(heading_parser("jobnr. mode duration"))
+        .then_ignore(inline_whitespace_parser())
+        .ignore_then(resource_labels_parser())
         .padded()
         .then_ignore(separator_parser())
-        .ignore_then(
+        .then(
             text::whitespace()
                 .ignore_then(info)
-                .then_ignore(text::newline())
+                .then_ignore(row_end_parser())
                 .repeated(),
         )
 }
 
-pub(crate) fn resource_availability_parser() -> impl Parser<char, Vec<u8>, Error = Simple<char>> {
+pub(crate) fn resource_availability_parser(
+) -> impl Parser<char, (Vec<String>, Vec<u32>), Error = Simple<char>> {
     let separator = separator_parser();
 
-    let info = text::int(10)
-        .from_str::<u8>()
-        .unwrapped()
-        .then_ignore(just(' ').repeated())
+    let info = number_parser::<u32>()
+        .then_ignore(inline_whitespace_parser())
         .repeated()
-        .at_least(4);
+        .at_least(1);
 
     separator
-        .then_ignore(just("RESOURCEAVAILABILITIES:"))
+        .then_ignore(heading_parser("RESOURCEAVAILABILITIES:"))
         .padded()
-        .then_ignore(just("R 1  R 2  R 3  R 4"))
+        .ignore_then(resource_labels_parser())
         .padded()
-        .ignore_then(
+        .then(
             text::whitespace()
                 .ignore_then(info)
-                .then_ignore(text::newline()),
+                .then_ignore(row_end_parser()),
         )
 }
 
@@ -299,9 +407,10 @@ pub(crate) fn resource_availability_parser() -> impl Parser<char, Vec<u8>, Error
 mod tests {
     use chumsky::Parser;
 
-    use crate::parse_psp_lib;
+    use crate::{parse_psp_lib, PspLibParseError, PspLibRequestDuration};
 
     static TEST_FILE: &str = include_str!("../../examples/j1201_1.sm");
+    static MULTI_MODE_TEST_FILE: &str = include_str!("../../examples/multi_mode.mm");
 
     #[test]
     fn file_metadata_parsing() {
@@ -396,6 +505,187 @@ mod tests {
         assert!(output.is_ok());
     }
 
+    #[test]
+    fn multi_mode_parsing() {
+        let psp = parse_psp_lib(MULTI_MODE_TEST_FILE).unwrap();
+
+        assert!(psp.is_multi_mode());
+        assert_eq!(psp.request_durations.len(), 32);
+
+        let job_modes = psp.job_modes();
+        assert_eq!(job_modes.len(), 12);
+        assert_eq!(job_modes[1].modes.len(), 3);
+        assert_eq!(
+            job_modes[1].modes[1],
+            PspLibRequestDuration {
+                job_number: 2,
+                mode: 2,
+                duration: 5,
+                resources: vec![1, 9, 1, 0],
+            }
+        );
+        assert_eq!(psp.modes(11).len(), 3);
+        assert_eq!(psp.resource_availabilities.resources, vec![9, 9, 45, 42]);
+    }
+
+    #[test]
+    fn multi_project_parsing() {
+        let psp = parse_psp_lib(include_str!("../../examples/multi_project.sm")).unwrap();
+
+        assert_eq!(psp.projects, 2);
+        assert_eq!(psp.project_info[1].relative_date, 2);
+        assert_eq!(
+            psp.project_jobs()
+                .into_iter()
+                .map(|(info, jobs)| (info.number, jobs))
+                .collect::<Vec<_>>(),
+            vec![(1, 2..=4), (2, 5..=7)]
+        );
+    }
+
+    #[test]
+    fn single_mode_parsing() {
+        let psp = parse_psp_lib(TEST_FILE).unwrap();
+
+        assert!(!psp.is_multi_mode());
+        assert!(psp.job_modes().iter().all(|job| job.modes.len() == 1));
+    }
+
+    #[test]
+    fn arbitrary_resource_count_parsing() {
+        let mut in_resource_section = false;
+        let content = TEST_FILE
+            .replace(
+                "  - renewable                 :  4   R",
+                "  - renewable                 :  6   R",
+            )
+            .replace("R 1  R 2  R 3  R 4", "R 1  R 2  R 3  R 4  R 5  R 6")
+            .lines()
+            .map(|line| {
+                if line.starts_with("REQUESTS/DURATIONS:") {
+                    in_resource_section = true;
+                }
+
+                if in_resource_section
+                    && line.trim_start().starts_with(|c: char| c.is_ascii_digit())
+                {
+                    format!("{line}    2    3")
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+            + "\n";
+
+        let psp = parse_psp_lib(&content).unwrap();
+
+        assert_eq!(psp.resources.count(), 6);
+        assert!(psp
+            .request_durations
+            .iter()
+            .all(|request| request.resources.len() == 6));
+        assert_eq!(
+            psp.resource_availabilities.resources,
+            vec![14, 12, 13, 9, 2, 3]
+        );
+    }
+
+    #[test]
+    fn resource_count_mismatch() {
+        let content = TEST_FILE.replace(
+            "  - renewable                 :  4   R",
+            "  - renewable                 :  3   R",
+        );
+
+        assert!(matches!(
+            parse_psp_lib(&content),
+            Err(PspLibParseError::ResourceCountMismatch {
+                expected: 3,
+                found: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn wide_values_parsing() {
+        let content = TEST_FILE.replace("   14   12   13    9", " 1400 1200 1300  900");
+
+        let psp = parse_psp_lib(&content).unwrap();
+        assert_eq!(
+            psp.resource_availabilities.resources,
+            vec![1400, 1200, 1300, 900]
+        );
+
+        let content = TEST_FILE.replace("   14   12   13    9", " 99999999999 12 13 9");
+        assert!(parse_psp_lib(&content).is_err());
+
+        // Header values beyond the range of their field are parse errors as well
+        for (value, replacement) in [
+            ("24418", "99999999999999999999999"),
+            ("122", "99999999999999999999999"),
+        ] {
+            let content = TEST_FILE.replacen(value, replacement, 1);
+            let Err(PspLibParseError::ParseError(diagnostics)) = parse_psp_lib(&content) else {
+                panic!("expected a parse error");
+            };
+            assert_eq!(
+                diagnostics.0[0].reason.as_deref(),
+                Some("number 99999999999999999999999 is out of range")
+            );
+        }
+    }
+
+    #[test]
+    fn crlf_parsing() {
+        let content = TEST_FILE.replace('\n', "\r\n");
+
+        assert_eq!(
+            parse_psp_lib(&content).unwrap(),
+            parse_psp_lib(TEST_FILE).unwrap()
+        );
+    }
+
+    #[test]
+    fn lenient_whitespace_parsing() {
+        let content = TEST_FILE
+            .replace("R 1  R 2  R 3  R 4", "R1\tR 2 R  3\tR 4")
+            .replace(
+                "jobnr.    #modes  #successors   successors",
+                "jobnr. #modes\t#successors successors",
+            )
+            .replace(
+                "pronr.  #jobs rel.date duedate tardcost  MPM-Time",
+                "pronr.\t#jobs  rel.date duedate tardcost MPM-Time",
+            )
+            .replace(
+                "jobs (incl. supersource/sink )",
+                "jobs (incl. supersource/sink)",
+            )
+            .lines()
+            .map(|line| format!("{}  \t", line.replace("    ", "\t")))
+            .collect::<Vec<String>>()
+            .join("\n")
+            + "\n";
+
+        assert_eq!(
+            parse_psp_lib(&content).unwrap(),
+            parse_psp_lib(TEST_FILE).unwrap()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let psp = parse_psp_lib(MULTI_MODE_TEST_FILE).unwrap();
+
+        let json = serde_json::to_string(&psp).unwrap();
+        assert_eq!(
+            serde_json::from_str::<crate::PspLibProblem>(&json).unwrap(),
+            psp
+        );
+    }
+
     #[test]
     fn separator_parsing_fail() {
         let content = "asd";
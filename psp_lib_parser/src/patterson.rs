@@ -0,0 +1,142 @@
+//! Reader for the compact Patterson format (.rcp) used by the RCPLIB instance sets
+
+use chumsky::{prelude::*, Parser};
+
+use crate::{
+    number_parser,
+    structs::{
+        PspLibPrecedenceRelation, PspLibProblem, PspLibProblemResources, PspLibProjectInformation,
+        PspLibRequestDuration, PspLibResourceAvailability,
+    },
+    ParseDiagnostics, PspLibParseError,
+};
+
+/// Parses a Patterson file into the same problem model as `parse_psp_lib`.
+///
+/// The format consists of a line with the number of activities (incl. the dummy
+/// start and end activity) and resources, a line with the resource capacities and
+/// one entry per activity: duration, resource demands, successor count and successors.
+/// As only whitespace separates the numbers, entries may span multiple lines.
+pub fn parse_patterson(content: &str) -> Result<PspLibProblem, PspLibParseError> {
+    let numbers = patterson_parser()
+        .parse(content)
+        .map_err(|errors| PspLibParseError::ParseError(ParseDiagnostics::new(content, errors)))?;
+    let mut numbers = numbers.into_iter();
+    let mut next = || numbers.next().ok_or(PspLibParseError::UnexpectedEndOfInput);
+
+    let jobs = next()? as usize;
+    let resource_count = next()? as usize;
+
+    let availabilities = (0..resource_count)
+        .map(|_| next())
+        .collect::<Result<Vec<u32>, _>>()?;
+
+    // The job count is not trusted for allocations, truncated files may state any count
+    let mut precedence_relations = vec![];
+    let mut request_durations = vec![];
+
+    for job_number in 1..=jobs as u32 {
+        let duration = next()?;
+        let resources = (0..resource_count)
+            .map(|_| next())
+            .collect::<Result<Vec<u32>, _>>()?;
+        let successor_count = next()?;
+        let successors = (0..successor_count)
+            .map(|_| next())
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        request_durations.push(PspLibRequestDuration {
+            job_number,
+            mode: 1,
+            duration,
+            resources,
+        });
+        precedence_relations.push(PspLibPrecedenceRelation {
+            job_number,
+            mode_count: 1,
+            successor_count,
+            successors,
+            time_lags: vec![],
+        });
+    }
+
+    if next().is_ok() {
+        return Err(PspLibParseError::TrailingInput);
+    }
+
+    let horizon = request_durations
+        .iter()
+        .map(|request| request.duration as usize)
+        .sum();
+
+    Ok(PspLibProblem {
+        // Patterson files carry no generator metadata
+        file_with_basedata: String::new(),
+        initial_rng: 0,
+        projects: 1,
+        jobs,
+        horizon,
+        resources: PspLibProblemResources {
+            renewable: resource_count,
+            nonrenewable: 0,
+            doubly_constrained: 0,
+        },
+        project_info: vec![PspLibProjectInformation {
+            number: 1,
+            jobs: jobs.saturating_sub(2) as u32,
+            relative_date: 0,
+            due_date: 0,
+            tard_cost: 0,
+            mpm_time: 0,
+        }],
+        precedence_relations,
+        request_durations,
+        resource_availabilities: PspLibResourceAvailability {
+            resources: availabilities,
+        },
+    })
+}
+
+pub(crate) fn patterson_parser() -> impl Parser<char, Vec<u32>, Error = Simple<char>> {
+    number_parser::<u32>()
+        .padded()
+        .repeated()
+        .then_ignore(end())
+        .labelled("patterson")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_patterson, PspLibParseError};
+
+    static TEST_FILE: &str = include_str!("../../examples/patterson_example.rcp");
+
+    #[test]
+    fn parse_patterson_test() {
+        let psp = parse_patterson(TEST_FILE).unwrap();
+
+        assert_eq!(psp.jobs, 8);
+        assert_eq!(psp.resources.renewable, 2);
+        assert_eq!(psp.resource_availabilities.resources, vec![10, 8]);
+        assert_eq!(psp.horizon, 24);
+
+        assert_eq!(psp.precedence_relations[0].successors, vec![2, 3, 4]);
+        assert_eq!(psp.precedence_relations[2].successors, vec![5, 6]);
+        assert_eq!(psp.request_durations[1].duration, 4);
+        assert_eq!(psp.request_durations[1].resources, vec![6, 2]);
+    }
+
+    #[test]
+    fn truncated_patterson_fail() {
+        let content = &TEST_FILE[..TEST_FILE.len() - 10];
+
+        assert!(matches!(
+            parse_patterson(content),
+            Err(PspLibParseError::UnexpectedEndOfInput)
+        ));
+        assert!(matches!(
+            parse_patterson("4000000000 1\n10\n"),
+            Err(PspLibParseError::UnexpectedEndOfInput)
+        ));
+    }
+}
@@ -0,0 +1,259 @@
+//! Reader for ProGen/max RCPSP/max instances (.sch) with generalized precedence relations
+
+use chumsky::{prelude::*, Parser};
+
+use crate::{
+    number_parser,
+    structs::{
+        JobId, PspLibPrecedenceRelation, PspLibProblem, PspLibProblemResources,
+        PspLibProjectInformation, PspLibRequestDuration, PspLibResourceAvailability,
+    },
+    ParseDiagnostics, PspLibParseError,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Number(u32),
+    /// Bracketed time lags, one per mode combination
+    TimeLags(Vec<i32>),
+}
+
+/// Parses an RCPSP/max file into the same problem model as `parse_psp_lib`.
+///
+/// Activities are renumbered to start at 1, like the supersource of PSPLIB files.
+/// The time lag of every arc is kept in `PspLibPrecedenceRelation::time_lags`, maximal
+/// time lags appear as arcs with a negative lag. For multi-mode files only the lag
+/// between the first modes is kept.
+pub fn parse_rcpsp_max(content: &str) -> Result<PspLibProblem, PspLibParseError> {
+    let lines = rcpsp_max_parser()
+        .parse(content)
+        .map_err(|errors| PspLibParseError::ParseError(ParseDiagnostics::new(content, errors)))?;
+    let mut lines = lines.into_iter().enumerate();
+    let mut next_line = || {
+        lines
+            .next()
+            .ok_or(PspLibParseError::UnexpectedEndOfInput)
+            .and_then(|(index, tokens)| {
+                split_tokens(tokens)
+                    .map(|(numbers, time_lags)| (index + 1, numbers, time_lags))
+                    .ok_or(PspLibParseError::InvalidLine(index + 1))
+            })
+    };
+
+    let (_, header, _) = next_line()?;
+    let header_value = |index: usize| header.get(index).copied().unwrap_or_default() as usize;
+    if header.len() < 2 {
+        return Err(PspLibParseError::InvalidLine(1));
+    }
+    let jobs = header_value(0) + 2;
+    let resources = PspLibProblemResources {
+        renewable: header_value(1),
+        nonrenewable: header_value(2),
+        doubly_constrained: header_value(3),
+    };
+    let resource_count = resources.count();
+
+    // Neither the job nor the mode counts are trusted for allocations, as truncated files
+    // may state any count
+    let mut precedence_relations = vec![];
+    for _ in 0..jobs {
+        let (line, numbers, time_lags) = next_line()?;
+        if numbers.len() < 3 || numbers.len() != 3 + numbers[2] as usize {
+            return Err(PspLibParseError::InvalidLine(line));
+        }
+        if time_lags.len() != numbers[2] as usize {
+            return Err(PspLibParseError::InvalidLine(line));
+        }
+
+        precedence_relations.push(PspLibPrecedenceRelation {
+            job_number: numbers[0] + 1,
+            mode_count: numbers[1],
+            successor_count: numbers[2],
+            successors: numbers[3..].iter().map(|successor| successor + 1).collect(),
+            time_lags: time_lags
+                .into_iter()
+                .map(|lags| lags.first().copied().unwrap_or_default())
+                .collect(),
+        });
+    }
+
+    let mode_count: usize = precedence_relations
+        .iter()
+        .map(|relation| relation.mode_count.max(1) as usize)
+        .sum();
+    let mut request_durations: Vec<PspLibRequestDuration> = vec![];
+    for _ in 0..mode_count {
+        let (line, numbers, time_lags) = next_line()?;
+        if !time_lags.is_empty() {
+            return Err(PspLibParseError::InvalidLine(line));
+        }
+
+        // Follow-up modes of a job may omit the job number
+        let (job_number, mode_columns): (JobId, &[u32]) = if numbers.len() == resource_count + 3 {
+            (numbers[0] + 1, &numbers[1..])
+        } else if numbers.len() == resource_count + 2 && !request_durations.is_empty() {
+            (
+                request_durations[request_durations.len() - 1].job_number,
+                &numbers[..],
+            )
+        } else {
+            return Err(PspLibParseError::InvalidLine(line));
+        };
+
+        request_durations.push(PspLibRequestDuration {
+            job_number,
+            mode: mode_columns[0],
+            duration: mode_columns[1],
+            resources: mode_columns[2..].to_vec(),
+        });
+    }
+
+    let (line, availabilities, time_lags) = next_line()?;
+    if availabilities.len() != resource_count || !time_lags.is_empty() {
+        return Err(PspLibParseError::InvalidLine(line));
+    }
+
+    if next_line().is_ok() {
+        return Err(PspLibParseError::TrailingInput);
+    }
+
+    // Every job fits into the horizon even if it has to wait for its longest outgoing lag
+    let horizon = precedence_relations
+        .iter()
+        .map(|relation| {
+            let duration = request_durations
+                .iter()
+                .filter(|request| request.job_number == relation.job_number)
+                .map(|request| request.duration as i32)
+                .max()
+                .unwrap_or_default();
+
+            relation
+                .time_lags
+                .iter()
+                .copied()
+                .fold(duration, i32::max)
+                .max(0) as usize
+        })
+        .sum();
+
+    Ok(PspLibProblem {
+        // RCPSP/max files carry no generator metadata
+        file_with_basedata: String::new(),
+        initial_rng: 0,
+        projects: 1,
+        jobs,
+        horizon,
+        resources,
+        project_info: vec![PspLibProjectInformation {
+            number: 1,
+            jobs: (jobs - 2) as u32,
+            relative_date: 0,
+            due_date: 0,
+            tard_cost: 0,
+            mpm_time: 0,
+        }],
+        precedence_relations,
+        request_durations,
+        resource_availabilities: PspLibResourceAvailability {
+            resources: availabilities,
+        },
+    })
+}
+
+/// Splits a line into its leading numbers and trailing time lags, `None` if they interleave
+fn split_tokens(tokens: Vec<Token>) -> Option<(Vec<u32>, Vec<Vec<i32>>)> {
+    let mut numbers = vec![];
+    let mut time_lags = vec![];
+
+    for token in tokens {
+        match token {
+            Token::Number(_) if !time_lags.is_empty() => return None,
+            Token::Number(number) => numbers.push(number),
+            Token::TimeLags(lags) => time_lags.push(lags),
+        }
+    }
+
+    Some((numbers, time_lags))
+}
+
+pub(crate) fn rcpsp_max_parser() -> impl Parser<char, Vec<Vec<Token>>, Error = Simple<char>> {
+    let inline_whitespace = one_of(" \t\r").repeated();
+
+    let lag = just('-')
+        .or_not()
+        .then(number_parser::<i32>())
+        .map(|(sign, lag)| if sign.is_some() { -lag } else { lag });
+
+    let time_lags = lag
+        .padded_by(inline_whitespace.clone())
+        .repeated()
+        .at_least(1)
+        .delimited_by(just('['), just(']'))
+        .map(Token::TimeLags)
+        .labelled("time lags");
+
+    let token = number_parser::<u32>()
+        .map(Token::Number)
+        .or(time_lags)
+        .then_ignore(inline_whitespace.clone());
+
+    let line = inline_whitespace.ignore_then(token.repeated().at_least(1));
+
+    text::whitespace()
+        .ignore_then(line)
+        .repeated()
+        .then_ignore(text::whitespace())
+        .then_ignore(end())
+        .labelled("rcpsp/max")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_rcpsp_max, PspLibParseError};
+
+    static TEST_FILE: &str = include_str!("../../examples/rcpsp_max_example.sch");
+
+    #[test]
+    fn parse_rcpsp_max_test() {
+        let psp = parse_rcpsp_max(TEST_FILE).unwrap();
+
+        assert_eq!(psp.jobs, 7);
+        assert_eq!(psp.resources.renewable, 1);
+        assert_eq!(psp.resource_availabilities.resources, vec![3]);
+        assert_eq!(psp.request_durations.len(), 7);
+        assert_eq!(psp.request_durations[3].duration, 4);
+
+        let relation = &psp.precedence_relations[2];
+        assert_eq!(relation.job_number, 3);
+        assert_eq!(relation.successors, vec![5, 2]);
+        assert_eq!(relation.time_lags, vec![2, -6]);
+        assert_eq!(
+            relation.forward_successors().copied().collect::<Vec<_>>(),
+            vec![5]
+        );
+    }
+
+    #[test]
+    fn missing_time_lag_fail() {
+        let content = TEST_FILE.replace("\t[4]", "");
+
+        assert!(matches!(
+            parse_rcpsp_max(&content),
+            Err(PspLibParseError::InvalidLine(5))
+        ));
+    }
+
+    #[test]
+    fn truncated_rcpsp_max_fail() {
+        for content in [
+            "4000000000\t1\t0\t0\n",
+            "0\t1\t0\t0\n0\t4000000000\t0\n1\t1\t0\n",
+        ] {
+            assert!(matches!(
+                parse_rcpsp_max(content),
+                Err(PspLibParseError::UnexpectedEndOfInput)
+            ));
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Reader for the PSPLIB solution lists, i.e. the heuristic (`*hrs.sm`) and optimal
+//! (`*opt.sm`) makespans of the instance sets
+
+use std::{collections::HashMap, path::Path};
+
+use chumsky::{prelude::*, Parser};
+
+use crate::{number_parser, ParseDiagnostics, PspLibParseError};
+
+/// Identifies an instance within a PSPLIB set, e.g. `j3011_2.sm` is parameter 11, instance 2
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PspLibSolutionKey {
+    pub parameter: u32,
+    pub instance: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PspLibSolution {
+    /// Best-known or optimal makespan
+    pub makespan: u32,
+    pub author: Option<String>,
+    /// Submission date, only listed for heuristic results
+    pub date: Option<String>,
+    /// Runtime in seconds, only listed for optimal results
+    pub cpu_time: Option<f64>,
+}
+
+pub type PspLibSolutionList = HashMap<PspLibSolutionKey, PspLibSolution>;
+
+const SINGLE_MODE_SETS: [&str; 4] = ["120", "30", "60", "90"];
+const MULTI_MODE_SETS: [&str; 7] = ["10", "12", "14", "16", "18", "20", "30"];
+
+impl PspLibSolutionKey {
+    /// Derives the key from an instance file name such as `j3011_2.sm` or `j1010_1.mm`.
+    ///
+    /// This is the only way to find the key of an instance, the base data names stored in
+    /// the files do not identify instances, e.g. `j3011_2.sm` names `j30_27.bas`.
+    pub fn from_file_name(path: impl AsRef<Path>) -> Option<Self> {
+        let path = path.as_ref();
+        let file_name = path.file_name()?.to_str()?;
+        let (stem, extension) = file_name.split_once('.').unwrap_or((file_name, "sm"));
+        let sets: &[&str] = if extension.starts_with("mm") {
+            &MULTI_MODE_SETS
+        } else {
+            &SINGLE_MODE_SETS
+        };
+
+        let (set_and_parameter, instance) = stem.strip_prefix(['j', 'J'])?.split_once('_')?;
+        let parameter = sets
+            .iter()
+            .find_map(|set| set_and_parameter.strip_prefix(set))?;
+
+        Some(Self {
+            parameter: parameter.parse().ok()?,
+            instance: instance.parse().ok()?,
+        })
+    }
+}
+
+/// Decodes the raw contents of a solution list.
+///
+/// Some lists contain author names encoded as Latin-1, those are decoded as such.
+pub fn decode_solution_list(bytes: &[u8]) -> String {
+    String::from_utf8(bytes.to_vec())
+        .unwrap_or_else(|_| bytes.iter().map(|&byte| byte as char).collect())
+}
+
+/// Parses a PSPLIB solution list into a lookup by parameter and instance
+pub fn parse_solution_list(content: &str) -> Result<PspLibSolutionList, PspLibParseError> {
+    let lines = solution_list_parser()
+        .parse(content)
+        .map_err(|errors| PspLibParseError::ParseError(ParseDiagnostics::new(content, errors)))?;
+
+    // Lists of optimal solutions name their authors once in the header
+    let header_author = lines.iter().find_map(|line| match line {
+        SolutionLine::Other(text) => text
+            .trim_start()
+            .strip_prefix("Authors' Name")
+            .and_then(|text| text.split_once(':'))
+            .map(|(_, author)| author.trim().to_string()),
+        SolutionLine::Row { .. } => None,
+    });
+
+    let mut solutions = PspLibSolutionList::new();
+
+    for line in lines {
+        if let SolutionLine::Row {
+            parameter,
+            instance,
+            makespan,
+            rest,
+        } = line
+        {
+            let rest = rest.trim();
+            let solution = if let Ok(cpu_time) = rest.parse::<f64>() {
+                PspLibSolution {
+                    makespan,
+                    author: header_author.clone(),
+                    date: None,
+                    cpu_time: Some(cpu_time),
+                }
+            } else {
+                // Dates are formatted like `Tue Jan 30 16:52:23 2001`
+                let words: Vec<&str> = rest.split_whitespace().collect();
+                let (date, author) = words.split_at(words.len().min(5));
+
+                PspLibSolution {
+                    makespan,
+                    author: Some(author.join(" ")).filter(|author| !author.is_empty()),
+                    date: Some(date.join(" ")).filter(|date| !date.is_empty()),
+                    cpu_time: None,
+                }
+            };
+
+            solutions.insert(
+                PspLibSolutionKey {
+                    parameter,
+                    instance,
+                },
+                solution,
+            );
+        }
+    }
+
+    Ok(solutions)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SolutionLine {
+    Row {
+        parameter: u32,
+        instance: u32,
+        makespan: u32,
+        rest: String,
+    },
+    Other(String),
+}
+
+pub(crate) fn solution_list_parser() -> impl Parser<char, Vec<SolutionLine>, Error = Simple<char>> {
+    let inline_whitespace = one_of(" \t").repeated();
+    let rest_of_line = filter(|c: &char| *c != '\n' && *c != '\r')
+        .repeated()
+        .collect::<String>();
+
+    let row = inline_whitespace
+        .clone()
+        .ignore_then(number_parser::<u32>())
+        .then_ignore(inline_whitespace.clone().at_least(1))
+        .then(number_parser::<u32>())
+        .then_ignore(inline_whitespace.at_least(1))
+        .then(number_parser::<u32>())
+        .then(rest_of_line)
+        .map(
+            |(((parameter, instance), makespan), rest)| SolutionLine::Row {
+                parameter,
+                instance,
+                makespan,
+                rest,
+            },
+        )
+        .labelled("solution");
+
+    row.or(rest_of_line.map(SolutionLine::Other))
+        .separated_by(text::newline())
+        .then_ignore(end())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse_psp_lib, parse_solution_list,
+        solutions::{decode_solution_list, PspLibSolutionKey},
+        PspLibSolution,
+    };
+
+    static HEURISTIC_FILE: &[u8] = include_bytes!("../../examples/j120hrs.sm.txt");
+    static OPTIMAL_FILE: &str = include_str!("../../examples/j30opt.sm.txt");
+
+    #[test]
+    fn heuristic_solution_list_parsing() {
+        let solutions = parse_solution_list(&decode_solution_list(HEURISTIC_FILE)).unwrap();
+
+        assert_eq!(solutions.len(), 600);
+        assert_eq!(
+            solutions[&PspLibSolutionKey {
+                parameter: 1,
+                instance: 2
+            }],
+            PspLibSolution {
+                makespan: 109,
+                author: Some("P. Laborie".to_string()),
+                date: Some("Thu Jun 9 11:12:36 2005".to_string()),
+                cpu_time: None,
+            }
+        );
+        assert_eq!(
+            solutions[&PspLibSolutionKey {
+                parameter: 8,
+                instance: 2
+            }]
+                .author
+                .as_deref(),
+            Some("José Fernando Gonçalves & Jorge Magalhãe")
+        );
+    }
+
+    #[test]
+    fn optimal_solution_list_parsing() {
+        let solutions = parse_solution_list(OPTIMAL_FILE).unwrap();
+
+        assert_eq!(solutions.len(), 480);
+
+        let solution = &solutions[&PspLibSolutionKey {
+            parameter: 1,
+            instance: 1,
+        }];
+        assert_eq!(solution.makespan, 43);
+        assert_eq!(
+            solution.author.as_deref(),
+            Some("E. Demeulemeester / W. Herroelen")
+        );
+        assert_eq!(solution.cpu_time, Some(0.3));
+    }
+
+    #[test]
+    fn solution_key_from_file_name() {
+        assert_eq!(
+            PspLibSolutionKey::from_file_name("examples/j30.sm/j3011_2.sm"),
+            Some(PspLibSolutionKey {
+                parameter: 11,
+                instance: 2
+            })
+        );
+        assert_eq!(
+            PspLibSolutionKey::from_file_name("j12052_10.sm"),
+            Some(PspLibSolutionKey {
+                parameter: 52,
+                instance: 10
+            })
+        );
+        assert_eq!(
+            PspLibSolutionKey::from_file_name("j1210_1.mm"),
+            Some(PspLibSolutionKey {
+                parameter: 10,
+                instance: 1
+            })
+        );
+        assert_eq!(PspLibSolutionKey::from_file_name("pat1.rcp"), None);
+    }
+
+    #[test]
+    fn j30_solution_lookup() {
+        let solutions = parse_solution_list(OPTIMAL_FILE).unwrap();
+        let key = PspLibSolutionKey::from_file_name("examples/j30.sm/j301_1.sm").unwrap();
+
+        assert_eq!(
+            key,
+            PspLibSolutionKey {
+                parameter: 1,
+                instance: 1
+            }
+        );
+        assert_eq!(solutions[&key].makespan, 43);
+
+        // The J30 fixture names its base data after another parameter
+        let psp = parse_psp_lib(include_str!("../../examples/j3011_2.sm")).unwrap();
+        assert_eq!(psp.file_with_basedata, "j30_27.bas");
+        let key = PspLibSolutionKey::from_file_name("j3011_2.sm").unwrap();
+        assert_eq!(solutions[&key].makespan, 56);
+    }
+}
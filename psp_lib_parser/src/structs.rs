@@ -1,4 +1,10 @@
+use std::ops::RangeInclusive;
+
+/// Number of a job, as used in the PSPLIB files. The supersource is job 1.
+pub type JobId = u32;
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PspLibProblem {
     // file metadata
     pub file_with_basedata: String,
@@ -19,45 +25,209 @@ pub struct PspLibProblem {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PspLibProblemResources {
     pub renewable: usize,
     pub nonrenewable: usize,
     pub doubly_constrained: usize,
 }
 
+impl PspLibProblemResources {
+    /// Total number of resources, i.e. the number of resource columns
+    pub fn count(&self) -> usize {
+        self.renewable + self.nonrenewable + self.doubly_constrained
+    }
+
+    /// Kind of the resource in the given 0-based column
+    pub fn kind(&self, index: usize) -> Option<ResourceKind> {
+        if index < self.renewable {
+            Some(ResourceKind::Renewable)
+        } else if index < self.renewable + self.nonrenewable {
+            Some(ResourceKind::Nonrenewable)
+        } else if index < self.count() {
+            Some(ResourceKind::DoublyConstrained)
+        } else {
+            None
+        }
+    }
+
+    /// Columns of the resources whose capacity limits the usage in every period,
+    /// i.e. renewable and doubly constrained resources
+    pub fn per_period_indices(&self) -> Vec<usize> {
+        (0..self.count())
+            .filter(|&index| self.kind(index).is_some_and(ResourceKind::is_per_period))
+            .collect()
+    }
+
+    /// Columns of the resources whose capacity is a budget for the whole project,
+    /// i.e. nonrenewable and doubly constrained resources
+    pub fn budget_indices(&self) -> Vec<usize> {
+        (0..self.count())
+            .filter(|&index| self.kind(index).is_some_and(ResourceKind::is_budget))
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResourceKind {
+    /// Capacity is available anew in every period
+    Renewable,
+    /// Capacity is a budget consumed once per job, e.g. money
+    Nonrenewable,
+    /// Capacity is available per period and limits the total consumption as well
+    DoublyConstrained,
+}
+
+impl ResourceKind {
+    /// Whether the capacity limits the usage in every period
+    pub fn is_per_period(self) -> bool {
+        matches!(self, Self::Renewable | Self::DoublyConstrained)
+    }
+
+    /// Whether the capacity limits the consumption over the whole project
+    pub fn is_budget(self) -> bool {
+        matches!(self, Self::Nonrenewable | Self::DoublyConstrained)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PspLibProjectInformation {
-    pub number: u8,
-    pub jobs: u8,
-    pub relative_date: u8,
-    pub due_date: u8,
-    pub tard_cost: u8,
-    pub mpm_time: u8,
+    pub number: u32,
+    pub jobs: JobId,
+    pub relative_date: u32,
+    pub due_date: u32,
+    pub tard_cost: u32,
+    pub mpm_time: u32,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PspLibPrecedenceRelation {
-    pub job_number: u8,
-    pub mode_count: u8,
-    pub successor_count: u8,
-    pub successors: Vec<u8>,
+    pub job_number: JobId,
+    pub mode_count: u32,
+    pub successor_count: u32,
+    pub successors: Vec<JobId>,
+    /// Minimal start-to-start time lag per successor, as given by RCPSP/max instances.
+    /// Empty for plain finish-to-start relations.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_lags: Vec<i32>,
+}
+
+impl PspLibPrecedenceRelation {
+    /// Returns the successors that may not start before this job.
+    ///
+    /// Maximal time lags are encoded as arcs with a negative lag pointing back to the
+    /// earlier job, those are skipped.
+    pub fn forward_successors(&self) -> impl Iterator<Item = &JobId> {
+        self.successors
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.time_lags.get(*index).is_none_or(|lag| *lag >= 0))
+            .map(|(_, successor)| successor)
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PspLibRequestDuration {
-    pub job_number: u8,
-    pub mode: u8,
-    pub duration: u8,
-    pub r1: u8,
-    pub r2: u8,
-    pub r3: u8,
-    pub r4: u8,
+    pub job_number: JobId,
+    pub mode: u32,
+    pub duration: u32,
+    /// Per resource demand, ordered renewable, nonrenewable, doubly constrained
+    pub resources: Vec<u32>,
+}
+
+impl PspLibRequestDuration {
+    /// Amount of a budget resource consumed by executing this mode.
+    ///
+    /// Nonrenewable resources are consumed once, doubly constrained resources in
+    /// every period of the job.
+    pub fn consumption(&self, index: usize, kind: ResourceKind) -> u64 {
+        let demand = self.resources.get(index).copied().unwrap_or(0) as u64;
+
+        match kind {
+            ResourceKind::DoublyConstrained => demand * self.duration as u64,
+            ResourceKind::Renewable | ResourceKind::Nonrenewable => demand,
+        }
+    }
+
+    /// Whether no demand of this mode exceeds the capacity of its resource on its own,
+    /// modes of multi-mode instances may not be executable at all
+    pub fn is_executable(&self, capacities: &[u32]) -> bool {
+        self.resources
+            .iter()
+            .enumerate()
+            .all(|(index, demand)| *demand <= capacities.get(index).copied().unwrap_or(0))
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PspLibResourceAvailability {
-    pub r1: u8,
-    pub r2: u8,
-    pub r3: u8,
-    pub r4: u8,
+    /// Per resource capacity, ordered renewable, nonrenewable, doubly constrained
+    pub resources: Vec<u32>,
+}
+
+/// All execution modes of a single job, as found in multi-mode (.mm) instances
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PspLibJobModes {
+    pub job_number: JobId,
+    pub modes: Vec<PspLibRequestDuration>,
+}
+
+impl PspLibProblem {
+    /// Returns the jobs of every project, in the order of the project information.
+    ///
+    /// Projects are laid out consecutively after the supersource, each spanning its
+    /// number of non-dummy jobs.
+    pub fn project_jobs(&self) -> Vec<(&PspLibProjectInformation, RangeInclusive<JobId>)> {
+        let mut first_job = 2;
+
+        self.project_info
+            .iter()
+            .map(|info| {
+                let jobs = first_job..=first_job + info.jobs - 1;
+                first_job += info.jobs;
+
+                (info, jobs)
+            })
+            .collect()
+    }
+
+    /// Returns true if at least one job can be executed in more than one mode
+    pub fn is_multi_mode(&self) -> bool {
+        self.precedence_relations
+            .iter()
+            .any(|relation| relation.mode_count > 1)
+    }
+
+    /// Returns the request/duration entries of all modes of a given job
+    pub fn modes(&self, job_number: JobId) -> Vec<&PspLibRequestDuration> {
+        self.request_durations
+            .iter()
+            .filter(|request| request.job_number == job_number)
+            .collect()
+    }
+
+    /// Groups the request/duration entries by job, preserving the file order
+    pub fn job_modes(&self) -> Vec<PspLibJobModes> {
+        let mut jobs: Vec<PspLibJobModes> = vec![];
+
+        for request in &self.request_durations {
+            match jobs.last_mut() {
+                Some(job) if job.job_number == request.job_number => {
+                    job.modes.push(request.clone())
+                }
+                _ => jobs.push(PspLibJobModes {
+                    job_number: request.job_number,
+                    modes: vec![request.clone()],
+                }),
+            }
+        }
+
+        jobs
+    }
 }
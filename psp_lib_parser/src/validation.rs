@@ -0,0 +1,507 @@
+//! Semantic checks of parsed instances that the grammar alone cannot express
+
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+
+use crate::structs::{JobId, PspLibProblem};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    #[error("Expected {expected} jobs, but found {found}")]
+    JobCountMismatch { expected: usize, found: usize },
+    #[error("Job {job} is not numbered between 1 and {jobs}")]
+    JobNumberOutOfRange { job: JobId, jobs: usize },
+    #[error("Expected information on {expected} projects, but found {found}")]
+    ProjectCountMismatch { expected: usize, found: usize },
+    #[error("Projects span {found} jobs, but the instance has {expected} non-dummy jobs")]
+    ProjectJobCountMismatch { expected: usize, found: usize },
+    #[error("Job {job} declares {declared} successors, but lists {found}")]
+    SuccessorCountMismatch {
+        job: JobId,
+        declared: u32,
+        found: usize,
+    },
+    #[error("Job {job} lists successor {successor}, which does not exist")]
+    UnknownSuccessor { job: JobId, successor: JobId },
+    #[error("Job {job} lists successor {successor} more than once")]
+    DuplicateSuccessor { job: JobId, successor: JobId },
+    #[error("Job {job} has precedence relations, but no request/duration entry")]
+    MissingRequestDuration { job: JobId },
+    #[error("Precedence relations contain the cycle {}", format_cycle(.jobs))]
+    PrecedenceCycle { jobs: Vec<JobId> },
+    #[error("Job {job} demands {found} resources in mode {mode}, but the instance has {expected}")]
+    RequestResourceCountMismatch {
+        job: JobId,
+        mode: u32,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Expected the availability of {expected} resources, but found {found}")]
+    AvailabilityCountMismatch { expected: usize, found: usize },
+    #[error(
+        "Job {job} demands {demand} units of resource {resource} in mode {mode}, \
+         but only {capacity} are available"
+    )]
+    DemandExceedsCapacity {
+        job: JobId,
+        mode: u32,
+        /// 1-based resource column
+        resource: usize,
+        demand: u32,
+        capacity: u32,
+    },
+    #[error(
+        "Resource {resource} is consumed {minimum} units even in the cheapest modes, \
+         but its budget is {capacity}"
+    )]
+    BudgetExceeded {
+        /// 1-based resource column
+        resource: usize,
+        minimum: u64,
+        capacity: u32,
+    },
+}
+
+fn format_cycle(jobs: &[JobId]) -> String {
+    jobs.iter()
+        .chain(jobs.first())
+        .map(|job| job.to_string())
+        .collect::<Vec<String>>()
+        .join(" -> ")
+}
+
+/// Checks a parsed problem for inconsistencies that would make scheduling it fail.
+///
+/// Returns all found errors, an empty list means the problem is valid.
+pub fn validate_psp_lib(psp: &PspLibProblem) -> Vec<ValidationError> {
+    let mut errors = vec![];
+
+    let jobs: HashSet<JobId> = psp
+        .precedence_relations
+        .iter()
+        .map(|relation| relation.job_number)
+        .collect();
+
+    if jobs.len() != psp.jobs {
+        errors.push(ValidationError::JobCountMismatch {
+            expected: psp.jobs,
+            found: jobs.len(),
+        });
+    }
+
+    // Jobs are numbered consecutively, which the networks built from instances rely on
+    let mut out_of_range = HashSet::new();
+    for job in psp
+        .precedence_relations
+        .iter()
+        .map(|relation| relation.job_number)
+        .chain(
+            psp.request_durations
+                .iter()
+                .map(|request| request.job_number),
+        )
+    {
+        if !(1..=psp.jobs).contains(&(job as usize)) && out_of_range.insert(job) {
+            errors.push(ValidationError::JobNumberOutOfRange {
+                job,
+                jobs: psp.jobs,
+            });
+        }
+    }
+
+    if psp.project_info.len() != psp.projects {
+        errors.push(ValidationError::ProjectCountMismatch {
+            expected: psp.projects,
+            found: psp.project_info.len(),
+        });
+    }
+
+    let project_jobs: usize = psp.project_info.iter().map(|info| info.jobs as usize).sum();
+    if project_jobs + 2 != psp.jobs {
+        errors.push(ValidationError::ProjectJobCountMismatch {
+            expected: psp.jobs.saturating_sub(2),
+            found: project_jobs,
+        });
+    }
+
+    let requested_jobs: HashSet<JobId> = psp
+        .request_durations
+        .iter()
+        .map(|request| request.job_number)
+        .collect();
+
+    for relation in &psp.precedence_relations {
+        if relation.successor_count as usize != relation.successors.len() {
+            errors.push(ValidationError::SuccessorCountMismatch {
+                job: relation.job_number,
+                declared: relation.successor_count,
+                found: relation.successors.len(),
+            });
+        }
+
+        let mut listed = HashSet::new();
+        for successor in &relation.successors {
+            if !jobs.contains(successor) {
+                errors.push(ValidationError::UnknownSuccessor {
+                    job: relation.job_number,
+                    successor: *successor,
+                });
+            }
+            if !listed.insert(*successor) {
+                errors.push(ValidationError::DuplicateSuccessor {
+                    job: relation.job_number,
+                    successor: *successor,
+                });
+            }
+        }
+
+        if !requested_jobs.contains(&relation.job_number) {
+            errors.push(ValidationError::MissingRequestDuration {
+                job: relation.job_number,
+            });
+        }
+    }
+
+    if let Some(jobs) = find_cycle(psp) {
+        errors.push(ValidationError::PrecedenceCycle { jobs });
+    }
+
+    // Resources are looked up by column, every row has to list all of them
+    let resource_count = psp.resources.count();
+    let capacities = &psp.resource_availabilities.resources;
+    if capacities.len() != resource_count {
+        errors.push(ValidationError::AvailabilityCountMismatch {
+            expected: resource_count,
+            found: capacities.len(),
+        });
+    }
+    for request in &psp.request_durations {
+        if request.resources.len() != resource_count {
+            errors.push(ValidationError::RequestResourceCountMismatch {
+                job: request.job_number,
+                mode: request.mode,
+                expected: resource_count,
+                found: request.resources.len(),
+            });
+        }
+    }
+
+    // Modes demanding more than a capacity are left out when selecting modes, a job
+    // cannot be scheduled only if none of its modes remains
+    let job_modes = psp.job_modes();
+    for job in &job_modes {
+        if job.modes.iter().any(|mode| mode.is_executable(capacities)) {
+            continue;
+        }
+
+        for request in &job.modes {
+            for (index, (demand, capacity)) in request.resources.iter().zip(capacities).enumerate()
+            {
+                if demand > capacity {
+                    errors.push(ValidationError::DemandExceedsCapacity {
+                        job: request.job_number,
+                        mode: request.mode,
+                        resource: index + 1,
+                        demand: *demand,
+                        capacity: *capacity,
+                    });
+                }
+            }
+        }
+    }
+
+    // Budgets of nonrenewable and doubly constrained resources have to suffice for the
+    // cheapest executable mode of every job
+    for index in psp.resources.budget_indices() {
+        let Some(kind) = psp.resources.kind(index) else {
+            continue;
+        };
+
+        let minimum: u64 = job_modes
+            .iter()
+            .filter_map(|job| {
+                job.modes
+                    .iter()
+                    .filter(|mode| mode.is_executable(capacities))
+                    .map(|mode| mode.consumption(index, kind))
+                    .min()
+            })
+            .sum();
+        let capacity = capacities.get(index).copied().unwrap_or(0);
+
+        if minimum > capacity as u64 {
+            errors.push(ValidationError::BudgetExceeded {
+                resource: index + 1,
+                minimum,
+                capacity,
+            });
+        }
+    }
+
+    errors
+}
+
+/// Finds a cycle of jobs that transitively have to precede themselves.
+///
+/// Maximal time lags of RCPSP/max instances point backwards by design and are ignored.
+fn find_cycle(psp: &PspLibProblem) -> Option<Vec<JobId>> {
+    let successors: HashMap<JobId, Vec<JobId>> = psp
+        .precedence_relations
+        .iter()
+        .map(|relation| {
+            (
+                relation.job_number,
+                relation.forward_successors().copied().collect(),
+            )
+        })
+        .collect();
+
+    let mut finished: HashSet<JobId> = HashSet::new();
+
+    for start in psp
+        .precedence_relations
+        .iter()
+        .map(|relation| relation.job_number)
+    {
+        if finished.contains(&start) {
+            continue;
+        }
+
+        // Depth-first search, keeping the current path and each path entry's next successor
+        let mut path: Vec<(JobId, usize)> = vec![(start, 0)];
+        let mut on_path: HashSet<JobId> = HashSet::from([start]);
+
+        while let Some((job, next)) = path.last_mut() {
+            let job = *job;
+            let successor = successors
+                .get(&job)
+                .and_then(|successors| successors.get(*next))
+                .copied();
+            *next += 1;
+
+            match successor {
+                Some(successor) if on_path.contains(&successor) => {
+                    let cycle_start = path
+                        .iter()
+                        .position(|(job, _)| *job == successor)
+                        .unwrap_or_default();
+
+                    return Some(path[cycle_start..].iter().map(|(job, _)| *job).collect());
+                }
+                Some(successor) if !finished.contains(&successor) => {
+                    path.push((successor, 0));
+                    on_path.insert(successor);
+                }
+                Some(_) => {}
+                None => {
+                    path.pop();
+                    on_path.remove(&job);
+                    finished.insert(job);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_psp_lib, parse_rcpsp_max, validate_psp_lib, ValidationError};
+
+    static TEST_FILE: &str = include_str!("../../examples/j3011_2.sm");
+
+    #[test]
+    fn valid_examples() {
+        assert!(validate_psp_lib(&parse_psp_lib(TEST_FILE).unwrap()).is_empty());
+        assert!(validate_psp_lib(
+            &parse_psp_lib(include_str!("../../examples/multi_mode.mm")).unwrap()
+        )
+        .is_empty());
+        assert!(validate_psp_lib(
+            &parse_rcpsp_max(include_str!("../../examples/rcpsp_max_example.sch")).unwrap()
+        )
+        .is_empty());
+        assert!(validate_psp_lib(
+            &parse_psp_lib(include_str!("../../examples/multi_project.sm")).unwrap()
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn job_number_errors() {
+        // The sink renamed, its predecessors still refer to job 32
+        let mut psp = parse_psp_lib(TEST_FILE).unwrap();
+        psp.precedence_relations[31].job_number = 3_000_000_000;
+        psp.request_durations[31].job_number = 3_000_000_000;
+
+        let errors = validate_psp_lib(&psp);
+        assert_eq!(
+            errors[0],
+            ValidationError::JobNumberOutOfRange {
+                job: 3_000_000_000,
+                jobs: 32
+            }
+        );
+        assert!(errors[1..].iter().all(|error| matches!(
+            error,
+            ValidationError::UnknownSuccessor { successor: 32, .. }
+        )));
+    }
+
+    #[test]
+    fn project_errors() {
+        let mut psp = parse_psp_lib(TEST_FILE).unwrap();
+        psp.projects = 2;
+        psp.project_info[0].jobs = 28;
+
+        assert_eq!(
+            validate_psp_lib(&psp),
+            vec![
+                ValidationError::ProjectCountMismatch {
+                    expected: 2,
+                    found: 1
+                },
+                ValidationError::ProjectJobCountMismatch {
+                    expected: 30,
+                    found: 28
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn successor_errors() {
+        let mut psp = parse_psp_lib(TEST_FILE).unwrap();
+        psp.precedence_relations[1].successor_count = 4;
+        psp.precedence_relations[2].successors[0] = 40;
+        psp.precedence_relations[3].successors[1] = 6;
+
+        let errors = validate_psp_lib(&psp);
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::SuccessorCountMismatch {
+                    job: 2,
+                    declared: 4,
+                    found: 3
+                },
+                ValidationError::UnknownSuccessor {
+                    job: 3,
+                    successor: 40
+                },
+                ValidationError::DuplicateSuccessor {
+                    job: 4,
+                    successor: 6
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn precedence_cycle() {
+        let mut psp = parse_psp_lib(TEST_FILE).unwrap();
+        // 2 -> 8 -> 13 -> 15 -> 16 -> 17 -> 31 -> 32, close the cycle at 17
+        psp.precedence_relations[16].successors.push(8);
+        psp.precedence_relations[16].successor_count += 1;
+
+        let errors = validate_psp_lib(&psp);
+        assert_eq!(
+            errors,
+            vec![ValidationError::PrecedenceCycle {
+                jobs: vec![8, 13, 15, 16, 17]
+            }]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "Precedence relations contain the cycle 8 -> 13 -> 15 -> 16 -> 17 -> 8"
+        );
+    }
+
+    #[test]
+    fn resource_count_errors() {
+        let mut psp = parse_psp_lib(TEST_FILE).unwrap();
+        psp.request_durations[4].resources.pop();
+        psp.resource_availabilities.resources.push(10);
+
+        assert_eq!(
+            validate_psp_lib(&psp),
+            vec![
+                ValidationError::AvailabilityCountMismatch {
+                    expected: 4,
+                    found: 5
+                },
+                ValidationError::RequestResourceCountMismatch {
+                    job: 5,
+                    mode: 1,
+                    expected: 4,
+                    found: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn demand_exceeds_capacity() {
+        let mut psp = parse_psp_lib(TEST_FILE).unwrap();
+        psp.request_durations[4].resources[0] = 30;
+
+        assert_eq!(
+            validate_psp_lib(&psp),
+            vec![ValidationError::DemandExceedsCapacity {
+                job: 5,
+                mode: 1,
+                resource: 1,
+                demand: 30,
+                capacity: 29
+            }]
+        );
+    }
+
+    #[test]
+    fn non_executable_modes() {
+        // Mode 2 of job 2 demands 9 units of R 2, mode 3 demands 9 units of R 1
+        let mut psp = parse_psp_lib(include_str!("../../examples/multi_mode.mm")).unwrap();
+        psp.resource_availabilities.resources[..2].copy_from_slice(&[8, 8]);
+        assert_eq!(validate_psp_lib(&psp), vec![]);
+
+        psp.request_durations[1].resources[0] = 9;
+        assert_eq!(
+            validate_psp_lib(&psp),
+            [(1, 1), (2, 2), (3, 1)]
+                .into_iter()
+                .map(|(mode, resource)| ValidationError::DemandExceedsCapacity {
+                    job: 2,
+                    mode,
+                    resource,
+                    demand: 9,
+                    capacity: 8
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn budget_exceeded() {
+        let mut psp = parse_psp_lib(include_str!("../../examples/multi_mode.mm")).unwrap();
+        // Every mode of jobs 2 and 3 consumes 8 units of the first nonrenewable resource
+        for request in psp
+            .request_durations
+            .iter_mut()
+            .filter(|request| (2..=3).contains(&request.job_number))
+        {
+            request.resources[2] = 8;
+        }
+        psp.resource_availabilities.resources[2] = 10;
+
+        assert_eq!(
+            validate_psp_lib(&psp),
+            vec![ValidationError::BudgetExceeded {
+                resource: 3,
+                minimum: 16,
+                capacity: 10
+            }]
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Writer rendering problems in the PSPLIB (.sm/.mm) layout accepted by `parse_psp_lib`
+
+use std::fmt::Write;
+
+use thiserror::Error;
+
+use crate::structs::{JobId, PspLibProblem};
+
+const SEPARATOR: &str = "************************************************************************";
+const DASHED_SEPARATOR: &str =
+    "------------------------------------------------------------------------";
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PspLibWriteError {
+    #[error("Job {job} has time lags, which the PSPLIB layout cannot express")]
+    TimeLags { job: JobId },
+}
+
+/// Renders a problem in the PSPLIB layout.
+///
+/// Parsing the output yields the given problem again. Time lags of RCPSP/max
+/// instances cannot be expressed in this layout, such instances are rejected.
+pub fn write_psp_lib(psp: &PspLibProblem) -> Result<String, PspLibWriteError> {
+    if let Some(relation) = psp
+        .precedence_relations
+        .iter()
+        .find(|relation| !relation.time_lags.is_empty())
+    {
+        return Err(PspLibWriteError::TimeLags {
+            job: relation.job_number,
+        });
+    }
+
+    let mut output = String::new();
+
+    // Writing to a string never fails
+    write_sections(&mut output, psp).unwrap();
+
+    Ok(output)
+}
+
+/// Columns are aligned like the PSPLIB files, values wider than their column still keep
+/// a space to the previous one
+fn write_sections(output: &mut String, psp: &PspLibProblem) -> std::fmt::Result {
+    writeln!(output, "{SEPARATOR}")?;
+    writeln!(
+        output,
+        "file with basedata            : {}",
+        psp.file_with_basedata
+    )?;
+    writeln!(
+        output,
+        "initial value random generator: {}",
+        psp.initial_rng
+    )?;
+    writeln!(output, "{SEPARATOR}")?;
+
+    writeln!(output, "projects                      :  {}", psp.projects)?;
+    writeln!(output, "jobs (incl. supersource/sink ):  {}", psp.jobs)?;
+    writeln!(output, "horizon                       :  {}", psp.horizon)?;
+    writeln!(output, "RESOURCES")?;
+    writeln!(
+        output,
+        "  - renewable                 :  {}   R",
+        psp.resources.renewable
+    )?;
+    writeln!(
+        output,
+        "  - nonrenewable              :  {}   N",
+        psp.resources.nonrenewable
+    )?;
+    writeln!(
+        output,
+        "  - doubly constrained        :  {}   D",
+        psp.resources.doubly_constrained
+    )?;
+    writeln!(output, "{SEPARATOR}")?;
+
+    writeln!(output, "PROJECT INFORMATION:")?;
+    writeln!(output, "pronr.  #jobs rel.date duedate tardcost  MPM-Time")?;
+    for info in &psp.project_info {
+        writeln!(
+            output,
+            "{:>5} {:>6} {:>6} {:>8} {:>8} {:>8}",
+            info.number,
+            info.jobs,
+            info.relative_date,
+            info.due_date,
+            info.tard_cost,
+            info.mpm_time
+        )?;
+    }
+    writeln!(output, "{SEPARATOR}")?;
+
+    writeln!(output, "PRECEDENCE RELATIONS:")?;
+    writeln!(output, "jobnr.    #modes  #successors   successors")?;
+    for relation in &psp.precedence_relations {
+        write!(
+            output,
+            "{:>4} {:>8} {:>10}        ",
+            relation.job_number, relation.mode_count, relation.successor_count
+        )?;
+        for successor in &relation.successors {
+            write!(output, " {successor:>3}")?;
+        }
+        writeln!(output)?;
+    }
+    writeln!(output, "{SEPARATOR}")?;
+
+    let resource_labels = resource_labels(psp);
+
+    writeln!(output, "REQUESTS/DURATIONS:")?;
+    writeln!(output, "jobnr. mode duration{resource_labels}")?;
+    writeln!(output, "{DASHED_SEPARATOR}")?;
+    let mut previous_job = None;
+    for request in &psp.request_durations {
+        // Follow-up modes of a job omit the job number
+        if previous_job == Some(request.job_number) {
+            write!(output, "   ")?;
+        } else {
+            write!(output, "{:>3}", request.job_number)?;
+        }
+        previous_job = Some(request.job_number);
+
+        write!(output, " {:>6} {:>5}", request.mode, request.duration)?;
+        for (index, resource) in request.resources.iter().enumerate() {
+            if index == 0 {
+                write!(output, " {resource:>7}")?;
+            } else {
+                write!(output, " {resource:>4}")?;
+            }
+        }
+        writeln!(output)?;
+    }
+    writeln!(output, "{SEPARATOR}")?;
+
+    writeln!(output, "RESOURCEAVAILABILITIES:")?;
+    writeln!(output, "{resource_labels}")?;
+    for resource in &psp.resource_availabilities.resources {
+        write!(output, " {resource:>4}")?;
+    }
+    writeln!(output)?;
+    writeln!(output, "{SEPARATOR}")?;
+
+    Ok(())
+}
+
+/// Column labels of all resources, e.g. `  R 1  R 2  N 1`
+fn resource_labels(psp: &PspLibProblem) -> String {
+    [
+        ('R', psp.resources.renewable),
+        ('N', psp.resources.nonrenewable),
+        ('D', psp.resources.doubly_constrained),
+    ]
+    .into_iter()
+    .flat_map(|(kind, count)| (1..=count).map(move |number| format!("  {kind} {number}")))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PspLibWriteError;
+    use crate::{parse_patterson, parse_psp_lib, parse_rcpsp_max, write_psp_lib};
+
+    static TEST_FILE: &str = include_str!("../../examples/j1201_1.sm");
+    static MULTI_MODE_TEST_FILE: &str = include_str!("../../examples/multi_mode.mm");
+
+    #[test]
+    fn write_psp_lib_identical_text() {
+        let psp = parse_psp_lib(TEST_FILE).unwrap();
+
+        assert_eq!(write_psp_lib(&psp).unwrap(), TEST_FILE);
+    }
+
+    #[test]
+    fn write_psp_lib_multi_mode_round_trip() {
+        let psp = parse_psp_lib(MULTI_MODE_TEST_FILE).unwrap();
+
+        assert_eq!(parse_psp_lib(&write_psp_lib(&psp).unwrap()).unwrap(), psp);
+    }
+
+    #[test]
+    fn write_psp_lib_round_trip_examples() {
+        let folder = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples/j30.sm");
+
+        for entry in folder.read_dir().unwrap() {
+            let content = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+            let psp = parse_psp_lib(&content).unwrap();
+
+            assert_eq!(parse_psp_lib(&write_psp_lib(&psp).unwrap()).unwrap(), psp);
+        }
+    }
+
+    #[test]
+    fn write_psp_lib_converted_round_trip() {
+        // Neither format names a base data file
+        let psp = parse_patterson(include_str!("../../examples/patterson_example.rcp")).unwrap();
+        assert_eq!(psp.file_with_basedata, "");
+        assert_eq!(parse_psp_lib(&write_psp_lib(&psp).unwrap()).unwrap(), psp);
+
+        let mut psp =
+            parse_rcpsp_max(include_str!("../../examples/rcpsp_max_example.sch")).unwrap();
+        assert_eq!(
+            write_psp_lib(&psp),
+            Err(PspLibWriteError::TimeLags { job: 1 })
+        );
+        for relation in &mut psp.precedence_relations {
+            relation.time_lags.clear();
+        }
+        assert_eq!(parse_psp_lib(&write_psp_lib(&psp).unwrap()).unwrap(), psp);
+    }
+
+    #[test]
+    fn write_psp_lib_modified_instance() {
+        let mut psp = parse_psp_lib(TEST_FILE).unwrap();
+        psp.resource_availabilities.resources = psp
+            .resource_availabilities
+            .resources
+            .iter()
+            .map(|capacity| capacity * 2)
+            .collect();
+        psp.precedence_relations[0].successors.pop();
+        psp.precedence_relations[0].successor_count -= 1;
+
+        assert_eq!(parse_psp_lib(&write_psp_lib(&psp).unwrap()).unwrap(), psp);
+    }
+
+    #[test]
+    fn write_psp_lib_wide_values_round_trip() {
+        // Values exceeding the width of every column
+        let mut psp = parse_psp_lib(TEST_FILE).unwrap();
+        for relation in &mut psp.precedence_relations {
+            relation.job_number *= 1000;
+            relation.mode_count *= 1_000_000_000;
+            for successor in &mut relation.successors {
+                *successor *= 1000;
+            }
+        }
+        for request in &mut psp.request_durations {
+            request.job_number *= 1000;
+            request.mode *= 1_000_000;
+            request.duration *= 100_000;
+            for demand in &mut request.resources {
+                *demand = *demand * 10_000 + 10_000;
+            }
+        }
+        for capacity in &mut psp.resource_availabilities.resources {
+            *capacity *= 100_000;
+        }
+        let info = &mut psp.project_info[0];
+        (info.jobs, info.due_date, info.tard_cost, info.mpm_time) =
+            (1_000_000, 1_000_000_000, 1_000_000_000, 1_000_000_000);
+
+        let written = write_psp_lib(&psp).unwrap();
+        assert_eq!(parse_psp_lib(&written).unwrap(), psp);
+    }
+}
@@ -1,5 +1,7 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use psp_lib_parser::parse_psp_lib;
+use rcpsp::dag::Decoder;
+use rcpsp::portfolio::Objective;
 use rcpsp::scheduler::{custom, rayon, rayon_multi, SchedulerOptions};
 
 struct BenchmarkSet<'a> {
@@ -25,6 +27,8 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 parallel: false,
                 schedule_count: 10,
                 schedule_duration: None,
+                objective: Objective::Makespan,
+                decoder: Decoder::Serial,
             },
         },
         BenchmarkSet {
@@ -38,6 +42,8 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 parallel: true,
                 schedule_count: 10,
                 schedule_duration: None,
+                objective: Objective::Makespan,
+                decoder: Decoder::Serial,
             },
         },
         BenchmarkSet {
@@ -51,6 +57,8 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 parallel: false,
                 schedule_count: 10,
                 schedule_duration: None,
+                objective: Objective::Makespan,
+                decoder: Decoder::Serial,
             },
         },
         BenchmarkSet {
@@ -64,6 +72,8 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 parallel: true,
                 schedule_count: 10,
                 schedule_duration: None,
+                objective: Objective::Makespan,
+                decoder: Decoder::Serial,
             },
         },
         BenchmarkSet {
@@ -77,6 +87,8 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 parallel: false,
                 schedule_count: 10,
                 schedule_duration: None,
+                objective: Objective::Makespan,
+                decoder: Decoder::Serial,
             },
         },
         BenchmarkSet {
@@ -90,6 +102,8 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 parallel: true,
                 schedule_count: 10,
                 schedule_duration: None,
+                objective: Objective::Makespan,
+                decoder: Decoder::Serial,
             },
         },
     ];
@@ -113,7 +127,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 format!("{file}/{}", config.number_of_iterations),
             ),
             &config,
-            |b, config| b.iter(|| rayon::scheduler(psp.clone(), config.clone())),
+            |b, config| b.iter(|| rayon::scheduler(psp.clone(), config.clone()).unwrap()),
         );
         group.bench_with_input(
             BenchmarkId::new(
@@ -128,7 +142,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 format!("{file}/{}", config.number_of_iterations),
             ),
             &config,
-            |b, config| b.iter(|| custom::scheduler(psp.clone(), config.clone())),
+            |b, config| b.iter(|| custom::scheduler(psp.clone(), config.clone()).unwrap()),
         );
         group.bench_with_input(
             BenchmarkId::new(
@@ -143,7 +157,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 format!("{file}/{}", config.number_of_iterations),
             ),
             &config,
-            |b, config| b.iter(|| rayon_multi::scheduler(psp.clone(), config.clone())),
+            |b, config| b.iter(|| rayon_multi::scheduler(psp.clone(), config.clone()).unwrap()),
         );
     }
     group.finish();
@@ -0,0 +1,232 @@
+//! Lower bounds of the makespan for the modes assigned by a DAG
+//!
+//! All bounds relax the problem in a different way, hence their maximum is used by the
+//! schedulers to stop the search early and to rate the quality of the found schedules.
+
+use std::collections::BTreeSet;
+
+use psp_lib_parser::structs::JobId;
+
+use crate::dag::DAG;
+
+/// Capacity bound (LB2): total work of a resource divided by its capacity
+pub fn capacity_bound(dag: &DAG) -> usize {
+    let capacities = &dag.psp.resource_availabilities.resources;
+
+    dag.psp
+        .resources
+        .per_period_indices()
+        .into_iter()
+        .filter(|&index| capacities[index] > 0)
+        .map(|index| {
+            let work: usize = jobs(dag)
+                .map(|job| demand(dag, job, index) as usize * duration(dag, job))
+                .sum();
+
+            work.div_ceil(capacities[index] as usize)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Node packing bound (LB3) of Mingozzi et al.: total duration of a set of jobs of which
+/// no two can be executed in parallel.
+///
+/// Two jobs exclude each other if one has to precede the other, or if their combined
+/// demand exceeds the capacity of a resource. The set is packed greedily, starting from
+/// every single job.
+pub fn node_packing_bound(dag: &DAG) -> usize {
+    let jobs: Vec<JobId> = jobs(dag).collect();
+    let capacities = &dag.psp.resource_availabilities.resources;
+    let limited_resources = dag.psp.resources.per_period_indices();
+
+    // Generalized time lags may let successors start before their predecessor finished
+    let finish_to_start = dag
+        .psp
+        .precedence_relations
+        .iter()
+        .all(|relation| relation.time_lags.is_empty());
+
+    let excludes = |a: usize, b: usize| {
+        (finish_to_start && (dag.precedes(jobs[a], jobs[b]) || dag.precedes(jobs[b], jobs[a])))
+            || limited_resources.iter().any(|&index| {
+                demand(dag, jobs[a], index) + demand(dag, jobs[b], index) > capacities[index]
+            })
+    };
+
+    let mut by_duration: Vec<usize> = (0..jobs.len()).collect();
+    by_duration.sort_unstable_by_key(|&index| std::cmp::Reverse(duration(dag, jobs[index])));
+
+    by_duration
+        .iter()
+        .map(|&first| {
+            let mut packed = vec![first];
+            for &candidate in by_duration.iter().filter(|&&candidate| candidate != first) {
+                if packed.iter().all(|&job| excludes(job, candidate)) {
+                    packed.push(candidate);
+                }
+            }
+
+            packed.iter().map(|&job| duration(dag, jobs[job])).sum()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Energetic reasoning bound: the smallest makespan for which the minimal work every job
+/// has to perform within any time interval, given its CPM time window, fits the capacity.
+///
+/// The search starts at `start`, e.g. the best of the other bounds.
+pub fn energetic_bound(dag: &DAG, start: usize) -> usize {
+    let mut lower = start.max(dag.compute_cpm_table(None).horizon);
+    let mut upper = dag.compute_upper_bound().max(lower);
+
+    // Wider windows never require more work, thus the makespans without a conflict are
+    // found by a binary search
+    while lower < upper {
+        let makespan = lower + (upper - lower) / 2;
+
+        if has_energetic_conflict(dag, makespan) {
+            lower = makespan + 1;
+        } else {
+            upper = makespan;
+        }
+    }
+
+    lower
+}
+
+/// Largest of the critical path, capacity, node packing and energetic bounds
+pub fn best_lower_bound(dag: &DAG) -> usize {
+    let bound = dag
+        .compute_lower_bound(false)
+        .map(|(length, _)| length)
+        .unwrap_or(0)
+        .max(capacity_bound(dag))
+        .max(node_packing_bound(dag));
+
+    energetic_bound(dag, bound)
+}
+
+/// Whether any interval between the start and finish times of the jobs requires more
+/// work than the capacity provides, if the project has to be finished by `makespan`
+fn has_energetic_conflict(dag: &DAG, makespan: usize) -> bool {
+    let table = dag.compute_cpm_table(Some(makespan));
+    if table.horizon > makespan {
+        return true;
+    }
+
+    let capacities = &dag.psp.resource_availabilities.resources;
+    let limited_resources = dag.psp.resources.per_period_indices();
+
+    let interval_starts: BTreeSet<usize> = table
+        .jobs
+        .iter()
+        .flat_map(|timing| [timing.earliest_start, timing.latest_start])
+        .collect();
+    let interval_ends: BTreeSet<usize> = table
+        .jobs
+        .iter()
+        .flat_map(|timing| [timing.earliest_finish, timing.latest_finish])
+        .collect();
+
+    interval_starts.iter().any(|&from| {
+        interval_ends.range(from + 1..).any(|&to| {
+            limited_resources.iter().any(|&index| {
+                let work: usize = table
+                    .jobs
+                    .iter()
+                    .map(|timing| {
+                        // Work within the interval if the job is started as early or as
+                        // late as possible
+                        let left = timing
+                            .earliest_finish
+                            .min(to)
+                            .saturating_sub(timing.earliest_start.max(from));
+                        let right = timing
+                            .latest_finish
+                            .min(to)
+                            .saturating_sub(timing.latest_start.max(from));
+
+                        left.min(right) * demand(dag, timing.job, index) as usize
+                    })
+                    .sum();
+
+                work > capacities[index] as usize * (to - from)
+            })
+        })
+    })
+}
+
+/// All jobs of the instance, including the dummy jobs
+fn jobs(dag: &DAG) -> impl Iterator<Item = JobId> + '_ {
+    dag.psp
+        .precedence_relations
+        .iter()
+        .map(|relation| relation.job_number)
+}
+
+fn duration(dag: &DAG, job: JobId) -> usize {
+    dag.duration(job).unwrap_or(0) as usize
+}
+
+fn demand(dag: &DAG, job: JobId, index: usize) -> u32 {
+    dag.requests(job)
+        .and_then(|requests| requests.get(index))
+        .copied()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{
+        j30_network, j30_problem, network, patterson_network, EMPTY_INSTANCE, SINGLE_JOB_INSTANCE,
+    };
+
+    #[test]
+    fn bounds_below_optimum() {
+        // The optimal makespan of j3011_2 equals its critical path length of 56
+        let dag = j30_network();
+        let critical_path = dag.compute_lower_bound(false).unwrap().0;
+
+        assert_eq!(capacity_bound(&dag), 41);
+        assert!(node_packing_bound(&dag) <= 56);
+        assert!(energetic_bound(&dag, 0) >= critical_path);
+        assert!(energetic_bound(&dag, 0) <= 56);
+
+        assert_eq!(best_lower_bound(&dag), 56);
+    }
+
+    #[test]
+    fn disjunctive_jobs() {
+        // A single machine: no two jobs fit the capacity at once
+        let mut psp = j30_problem();
+        for request in &mut psp.request_durations[1..31] {
+            request.resources = vec![1, 0, 0, 0];
+        }
+        psp.resource_availabilities.resources = vec![1, 10, 10, 10];
+        let dag = network(psp);
+
+        let total_duration = dag.compute_upper_bound();
+        assert_eq!(capacity_bound(&dag), total_duration);
+        assert_eq!(node_packing_bound(&dag), total_duration);
+        assert_eq!(best_lower_bound(&dag), total_duration);
+    }
+
+    #[test]
+    fn degenerate_instances() {
+        let empty = patterson_network(EMPTY_INSTANCE);
+        assert_eq!(capacity_bound(&empty), 0);
+        assert_eq!(node_packing_bound(&empty), 0);
+        assert_eq!(energetic_bound(&empty, 0), 0);
+        assert_eq!(best_lower_bound(&empty), 0);
+
+        // The only job bounds the makespan on its own, its resources do not
+        let single_job = patterson_network(SINGLE_JOB_INSTANCE);
+        assert!(capacity_bound(&single_job) <= 5);
+        assert_eq!(node_packing_bound(&single_job), 5);
+        assert_eq!(energetic_bound(&single_job, 0), 5);
+        assert_eq!(best_lower_bound(&single_job), 5);
+    }
+}
@@ -0,0 +1,168 @@
+//! Standard indicators describing how hard an instance is to schedule
+
+use psp_lib_parser::structs::JobId;
+
+use crate::{bounds::capacity_bound, dag::DAG};
+
+/// Indicators of an instance, as used to classify the PSPLIB sets
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InstanceCharacteristics {
+    /// Number of non-dummy jobs
+    pub jobs: usize,
+    /// Number of resources limited per period
+    pub resources: usize,
+    /// Average number of precedence arcs per job, including the dummy jobs
+    pub network_complexity: f64,
+    /// Fraction of the pairs of non-dummy jobs ordered by a (transitive) precedence relation
+    pub order_strength: f64,
+    /// Average fraction of the resources requested by a non-dummy job
+    pub resource_factor: f64,
+    /// Average scarcity of the resources between 0 (tightest) and 1 (no conflicts)
+    pub resource_strength: f64,
+    /// Fraction of the pairs of non-dummy jobs that cannot be executed in parallel,
+    /// either due to precedence relations or their combined resource demand
+    pub disjunction_ratio: f64,
+    /// Length of the longest path through the network, ignoring resources
+    pub critical_path_length: usize,
+    /// Largest total work of a resource divided by its capacity, ignoring precedences
+    pub resource_lower_bound: usize,
+}
+
+impl InstanceCharacteristics {
+    /// Computes the indicators for the modes assigned by the DAG
+    pub fn new(dag: &DAG) -> Self {
+        let psp = &dag.psp;
+        let sink = psp.jobs as JobId;
+
+        let real_jobs: Vec<JobId> = psp
+            .precedence_relations
+            .iter()
+            .map(|relation| relation.job_number)
+            .filter(|job| *job != 1 && *job != sink)
+            .collect();
+        let job_count = real_jobs.len();
+        let job_pairs = (job_count * job_count.saturating_sub(1) / 2).max(1) as f64;
+
+        let arcs: usize = psp
+            .precedence_relations
+            .iter()
+            .map(|relation| relation.forward_successors().count())
+            .sum();
+        let network_complexity = arcs as f64 / psp.jobs.max(1) as f64;
+
+        let is_ordered = |a: &JobId, b: &JobId| dag.precedes(*a, *b) || dag.precedes(*b, *a);
+
+        let ordered_pairs = pairs(&real_jobs).filter(|(a, b)| is_ordered(a, b)).count();
+        let order_strength = ordered_pairs as f64 / job_pairs;
+
+        let cpm_table = dag.compute_cpm_table(None);
+        let critical_path_length = cpm_table.horizon;
+
+        let limited_resources = psp.resources.per_period_indices();
+        let capacities = &psp.resource_availabilities.resources;
+        let demand = |job: &JobId, index: usize| {
+            dag.requests(*job)
+                .and_then(|requests| requests.get(index))
+                .copied()
+                .unwrap_or(0)
+        };
+
+        let requested = real_jobs
+            .iter()
+            .flat_map(|job| limited_resources.iter().map(move |index| (job, *index)))
+            .filter(|(job, index)| demand(job, *index) > 0)
+            .count();
+        let resource_factor = if limited_resources.is_empty() || real_jobs.is_empty() {
+            0.0
+        } else {
+            requested as f64 / (job_count * limited_resources.len()) as f64
+        };
+
+        let resource_strengths: Vec<f64> = limited_resources
+            .iter()
+            .map(|&index| {
+                // Capacity relative to the largest single demand and the peak demand of the
+                // earliest start schedule
+                let min_capacity = real_jobs
+                    .iter()
+                    .map(|job| demand(job, index))
+                    .max()
+                    .unwrap_or(0);
+
+                let mut usage = vec![0_u32; critical_path_length];
+                for timing in &cpm_table.jobs {
+                    for used in &mut usage[timing.earliest_start..timing.earliest_finish] {
+                        *used += demand(&timing.job, index);
+                    }
+                }
+                let max_capacity = usage.into_iter().max().unwrap_or(0);
+
+                if max_capacity <= min_capacity {
+                    1.0
+                } else {
+                    (capacities[index] as f64 - min_capacity as f64)
+                        / (max_capacity - min_capacity) as f64
+                }
+            })
+            .collect();
+        let resource_strength = if resource_strengths.is_empty() {
+            1.0
+        } else {
+            resource_strengths.iter().sum::<f64>() / resource_strengths.len() as f64
+        };
+
+        let disjunct_pairs = pairs(&real_jobs)
+            .filter(|(a, b)| {
+                is_ordered(a, b)
+                    || limited_resources
+                        .iter()
+                        .any(|&index| demand(a, index) + demand(b, index) > capacities[index])
+            })
+            .count();
+        let disjunction_ratio = disjunct_pairs as f64 / job_pairs;
+
+        let resource_lower_bound = capacity_bound(dag);
+
+        Self {
+            jobs: job_count,
+            resources: limited_resources.len(),
+            network_complexity,
+            order_strength,
+            resource_factor,
+            resource_strength,
+            disjunction_ratio,
+            critical_path_length,
+            resource_lower_bound,
+        }
+    }
+}
+
+/// All unordered pairs of distinct jobs
+fn pairs(jobs: &[JobId]) -> impl Iterator<Item = (JobId, JobId)> + '_ {
+    jobs.iter()
+        .enumerate()
+        .flat_map(move |(index, a)| jobs[index + 1..].iter().map(move |b| (*a, *b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InstanceCharacteristics;
+    use crate::test_fixtures::j30_network;
+
+    #[test]
+    fn j30_characteristics() {
+        let characteristics = InstanceCharacteristics::new(&j30_network());
+
+        assert_eq!(characteristics.jobs, 30);
+        assert_eq!(characteristics.resources, 4);
+        assert_eq!(characteristics.network_complexity, 1.5);
+        assert!((characteristics.resource_factor - 0.75).abs() < 0.05);
+        assert!((characteristics.resource_strength - 0.7).abs() < 0.05);
+        assert!(characteristics.order_strength > 0.0 && characteristics.order_strength < 1.0);
+        assert!(characteristics.disjunction_ratio >= characteristics.order_strength);
+        // Equals the MPM-Time in the file
+        assert_eq!(characteristics.critical_path_length, 56);
+        assert_eq!(characteristics.resource_lower_bound, 41);
+    }
+}
@@ -1,59 +1,180 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
+
+use fixedbitset::FixedBitSet;
 use hashbrown::{HashMap, HashSet};
 
 use log::trace;
-use petgraph::algo;
-use petgraph::visit::NodeIndexable;
-use psp_lib_parser::structs::PspLibProblem;
+use psp_lib_parser::{
+    structs::{JobId, PspLibProblem},
+    validate_psp_lib, ValidationError,
+};
+
+use crate::{
+    bounds::best_lower_bound,
+    modes::{
+        assigned_mode, budget_violations, first_modes, repair_mode_assignment, BudgetViolation,
+        ModeAssignment,
+    },
+    portfolio::{Objective, ProjectOutcome},
+    resource_profile::ResourceProfile,
+    solution::{ScheduledJob, Solution},
+};
+
+/// Arc to or from a job, weighted by the minimal start-to-start time lag
+type Arc = (JobId, i32);
+
+/// Schedule generation scheme turning activity lists into start times
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Decoder {
+    /// Starts the jobs one after another, each as early as possible
+    #[default]
+    Serial,
+    /// Advances in time and starts all jobs fitting at each point, in list order
+    Parallel,
+    /// Decodes with both schemes and keeps the schedule with the better objective value
+    BestOf,
+}
+
+/// Critical path method timing of a single job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JobTiming {
+    pub job: JobId,
+    pub earliest_start: usize,
+    pub earliest_finish: usize,
+    pub latest_start: usize,
+    pub latest_finish: usize,
+    /// Time the job can be delayed without delaying the project beyond the horizon
+    pub total_float: usize,
+    /// Time the job can be delayed without delaying the earliest start of any successor
+    pub free_float: usize,
+}
+
+/// Timing of all jobs, ignoring resources and release dates
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CpmTable {
+    /// Time the latest start and finish times are computed against
+    pub horizon: usize,
+    /// Ordered by job number
+    pub jobs: Vec<JobTiming>,
+}
+
+impl CpmTable {
+    pub fn get(&self, job: JobId) -> Option<&JobTiming> {
+        self.jobs
+            .binary_search_by_key(&job, |timing| timing.job)
+            .ok()
+            .map(|index| &self.jobs[index])
+    }
+
+    /// Jobs without total float, ordered by job number
+    pub fn critical_jobs(&self) -> Vec<JobId> {
+        self.jobs
+            .iter()
+            .filter(|timing| timing.total_float == 0)
+            .map(|timing| timing.job)
+            .collect()
+    }
+}
+
+/// A job listed behind a job it has to start before
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrecedenceViolation {
+    /// Job listed later, although it has to start first
+    pub predecessor: JobId,
+    pub successor: JobId,
+}
+
+/// An instance rejected by [`DAG::new`], e.g. for a job demanding more than the
+/// capacity of a resource, which no schedule could accommodate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidInstance {
+    pub errors: Vec<ValidationError>,
+}
 
-// type Graph = petgraph::Graph<u8, u8>;
-// type NodeId = petgraph::graph::NodeIndex;
-type Graph = petgraph::matrix_graph::MatrixGraph<u8, u8>;
-type NodeId = petgraph::matrix_graph::NodeIndex;
+impl fmt::Display for InvalidInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid instance: ")?;
+        for (index, error) in self.errors.iter().enumerate() {
+            if index > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{error}")?;
+        }
 
+        Ok(())
+    }
+}
+
+impl std::error::Error for InvalidInstance {}
+
+/// Precedence network of an instance.
+///
+/// Jobs are stored densely, indexed by their job number, index 0 is unused.
 pub struct DAG {
-    durations: HashMap<NodeId, u8>,
-    graph: Graph,
-    job_to_nodes: HashMap<u8, NodeId>,
-    node_to_jobs: HashMap<NodeId, u8>,
+    durations: Vec<u32>,
+    /// Incoming arcs of every job
+    predecessors: Vec<Vec<Arc>>,
+    /// Outgoing arcs of every job
+    successors: Vec<Vec<Arc>>,
+    /// Jobs reachable from every job by arcs of non-negative time lags
+    closure: Vec<FixedBitSet>,
     pub psp: PspLibProblem,
 
-    requests: HashMap<u8, Vec<u8>>,
+    requests: Vec<Vec<u32>>,
+    modes: ModeAssignment,
+    /// Whether the assigned modes stay within all nonrenewable and doubly constrained budgets
+    feasible_modes: bool,
+    /// Earliest start of jobs belonging to projects released after time 0
+    release_dates: HashMap<JobId, usize>,
+    objective: Objective,
+    decoder: Decoder,
+    /// Indices of the renewable and doubly constrained resources
+    limited_resources: Vec<usize>,
     reduced_neighborhood: Vec<(usize, usize)>,
 }
 
 impl DAG {
-    pub fn new(psp: PspLibProblem, swap_range: usize) -> Self {
-        // let mut graph = petgraph::graph::DiGraph::<u8, u8>::new();
-        let mut graph = petgraph::matrix_graph::DiMatrix::<u8, u8>::new();
-
-        let mut job_to_nodes = HashMap::new();
-        let mut node_to_jobs = HashMap::new();
-        let mut durations = HashMap::new();
-        let mut requests = HashMap::new();
-
-        for duration in &psp.request_durations {
-            let node = graph.add_node(duration.duration);
-            job_to_nodes.insert(duration.job_number, node);
-            node_to_jobs.insert(node, duration.job_number);
-            durations.insert(node, duration.duration);
-        }
-
-        for relation in &psp.precedence_relations {
-            for successor in &relation.successors {
-                if let Some(job_index) = job_to_nodes.get(&(relation.job_number)) {
-                    if let Some(successor_index) = job_to_nodes.get(successor) {
-                        graph.add_edge(*job_index, *successor_index, 0);
-                    }
-                }
-            }
+    /// Builds the network of a problem, rejecting problems that fail
+    /// [`validate_psp_lib`], as those cannot be decoded into feasible schedules
+    pub fn new(psp: PspLibProblem, swap_range: usize) -> Result<Self, InvalidInstance> {
+        let errors = validate_psp_lib(&psp);
+        if !errors.is_empty() {
+            return Err(InvalidInstance { errors });
         }
 
-        for request in &psp.request_durations {
-            requests.insert(
-                request.job_number,
-                // (request.r1, request.r2, request.r3, request.r4),
-                vec![request.r1, request.r2, request.r3, request.r4],
-            );
+        // Durations, requests and arcs are set once the modes have been assigned
+        let size = psp
+            .request_durations
+            .iter()
+            .map(|request| request.job_number as usize)
+            .chain(psp.precedence_relations.iter().flat_map(|relation| {
+                std::iter::once(relation.job_number)
+                    .chain(relation.successors.iter().copied())
+                    .map(|job| job as usize)
+            }))
+            .max()
+            .unwrap_or(0)
+            .max(psp.jobs)
+            + 1;
+
+        // Multi-mode instances start out with each job's first mode, which is repaired
+        // if it exceeds a resource budget
+        let mut modes = first_modes(&psp);
+        if !budget_violations(&psp, &modes).is_empty() {
+            repair_mode_assignment(&psp, &mut modes);
+        }
+
+        let mut release_dates = HashMap::new();
+        for (info, jobs) in psp.project_jobs() {
+            if info.relative_date > 0 {
+                for job in jobs {
+                    release_dates.insert(job, info.relative_date as usize);
+                }
+            }
         }
 
         let mut reduced_neighborhood = Vec::new();
@@ -68,80 +189,402 @@ impl DAG {
             }
         }
 
-        Self {
-            durations,
-            graph,
-            job_to_nodes,
-            node_to_jobs,
+        let mut dag = Self {
+            durations: vec![0; size],
+            predecessors: vec![vec![]; size],
+            successors: vec![vec![]; size],
+            closure: transitive_closure(&psp, size),
+            limited_resources: psp.resources.per_period_indices(),
+            requests: vec![vec![0; psp.resource_availabilities.resources.len()]; size],
             psp,
-            requests,
+            modes: ModeAssignment::new(),
+            feasible_modes: true,
+            release_dates,
+            objective: Objective::default(),
+            decoder: Decoder::default(),
             reduced_neighborhood,
+        };
+        dag.assign_modes(modes);
+
+        Ok(dag)
+    }
+
+    /// Replaces the mode assignment, updating durations, requests and arcs.
+    ///
+    /// Schedules are rejected by the evaluation as long as the assignment exceeds a
+    /// resource budget, see [`DAG::repair_modes`].
+    pub fn with_mode_assignment(mut self, modes: ModeAssignment) -> Self {
+        self.assign_modes(modes);
+        self
+    }
+
+    /// The mode every job is executed in
+    pub fn mode_assignment(&self) -> &ModeAssignment {
+        &self.modes
+    }
+
+    /// Nonrenewable and doubly constrained resources consumed beyond their capacity
+    pub fn budget_violations(&self) -> Vec<BudgetViolation> {
+        budget_violations(&self.psp, &self.modes)
+    }
+
+    /// Switches modes until all resource budgets are kept, returns false if that failed
+    pub fn repair_modes(&mut self) -> bool {
+        let mut modes = self.modes.clone();
+        let repaired = repair_mode_assignment(&self.psp, &mut modes);
+        self.assign_modes(modes);
+
+        repaired
+    }
+
+    fn assign_modes(&mut self, modes: ModeAssignment) {
+        for job in 1..self.durations.len() as JobId {
+            if let Some(request) = assigned_mode(&self.psp, &modes, job) {
+                self.durations[job as usize] = request.duration;
+                self.requests[job as usize] = request.resources.clone();
+            }
         }
+
+        self.predecessors.iter_mut().for_each(Vec::clear);
+        self.successors.iter_mut().for_each(Vec::clear);
+        for relation in &self.psp.precedence_relations {
+            let job = relation.job_number;
+            for (index, successor) in relation.successors.iter().enumerate() {
+                // Without explicit time lags, a successor may start once the job finished
+                let time_lag = relation
+                    .time_lags
+                    .get(index)
+                    .copied()
+                    .unwrap_or(self.durations[job as usize] as i32);
+
+                // Arcs listed twice keep the last time lag
+                insert_arc(&mut self.successors[job as usize], (*successor, time_lag));
+                insert_arc(&mut self.predecessors[*successor as usize], (job, time_lag));
+            }
+        }
+
+        self.feasible_modes = budget_violations(&self.psp, &modes).is_empty();
+        self.modes = modes;
+    }
+
+    /// Sets the objective evaluated by [`DAG::compute_objective`]
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Sets the schedule generation scheme used to evaluate activity lists
+    pub fn with_decoder(mut self, decoder: Decoder) -> Self {
+        self.decoder = decoder;
+        self
+    }
+
+    /// Duration of a job in its assigned mode
+    pub fn duration(&self, job: JobId) -> Option<u32> {
+        self.durations
+            .get(job as usize)
+            .copied()
+            .filter(|_| job > 0)
+    }
+
+    /// Per resource demand of a job in its assigned mode
+    pub fn requests(&self, job: JobId) -> Option<&[u32]> {
+        self.requests
+            .get(job as usize)
+            .filter(|_| job > 0)
+            .map(Vec::as_slice)
     }
 
-    /// Compute the upper bound of execution time by accumulating all durations
+    /// Returns the minimal distance between the start of two jobs, if they are
+    /// connected by an arc.
+    ///
+    /// Negative lags represent maximal time lags of RCPSP/max instances.
+    pub fn time_lag(&self, from: JobId, to: JobId) -> Option<i32> {
+        self.successors
+            .get(from as usize)?
+            .iter()
+            .find(|(successor, _)| *successor == to)
+            .map(|(_, time_lag)| *time_lag)
+    }
+
+    /// Whether `before` has to start before `after`, following any path of arcs with
+    /// non-negative time lags.
+    ///
+    /// Activity lists are precedence feasible if no job is listed after a job it
+    /// precedes.
+    pub fn precedes(&self, before: JobId, after: JobId) -> bool {
+        self.closure
+            .get(before as usize)
+            .is_some_and(|reachable| reachable.contains(after as usize))
+    }
+
+    /// Compute the upper bound of execution time by accumulating all durations,
+    /// starting at the latest project release date
     pub fn compute_upper_bound(&self) -> usize {
-        self.psp
-            .request_durations
+        let latest_release = self.release_dates.values().max().copied().unwrap_or(0);
+
+        self.durations
             .iter()
-            .fold(0, |acc, duration| acc + (duration.duration as usize))
+            .fold(latest_release, |acc, duration| acc + (*duration as usize))
     }
-    /// Find the lower bound of execution time, based on the longest time in the graph
-    pub fn compute_lower_bound(&self, reversed: bool) -> Option<(usize, Vec<NodeId>)> {
-        let from = if !reversed {
-            self.graph.from_index(0)
-        } else {
-            self.graph.from_index(self.psp.jobs - 1)
+
+    /// Lower bound of the configured objective, at which the search may stop
+    pub fn compute_objective_lower_bound(&self) -> usize {
+        match self.objective {
+            Objective::Makespan => best_lower_bound(self),
+            Objective::TotalWeightedTardiness | Objective::TotalProjectDelay => 0,
+        }
+    }
+    /// Find the lower bound of execution time, based on the longest time in the graph.
+    ///
+    /// Computed by a forward pass over the jobs in topological order, or by a backward
+    /// pass in reverse order if `reversed` is set. Returns the length together with a
+    /// critical path of job numbers, leading from the supersource to the supersink or
+    /// from the supersink back to the supersource if reversed.
+    pub fn compute_lower_bound(&self, reversed: bool) -> Option<(usize, Vec<JobId>)> {
+        let order = self.topological_order();
+        let duration = |job: &JobId| self.duration(*job).unwrap_or(0) as i64;
+        let lag = |from: JobId, to: JobId| {
+            self.time_lag(from, to)
+                .map(i64::from)
+                .unwrap_or_else(|| duration(&from))
         };
-        let to = if !reversed {
-            self.graph.from_index(self.psp.jobs - 1)
+
+        // Longest distance from the start of the network to the end of every job, or from
+        // the start of every job to the end of the network, with the job it was reached by
+        let mut distances: HashMap<JobId, (i64, Option<JobId>)> = HashMap::new();
+        if !reversed {
+            for job in &order {
+                let (start, predecessor) = distances.get(job).copied().unwrap_or((0, None));
+                distances.insert(*job, (start + duration(job), predecessor));
+
+                for successor in self.forward_successors(*job) {
+                    let successor_start = start + lag(*job, successor);
+                    let entry = distances.entry(successor).or_insert((0, None));
+                    if successor_start > entry.0 || entry.1.is_none() {
+                        *entry = (successor_start.max(entry.0), Some(*job));
+                    }
+                }
+            }
         } else {
-            self.graph.from_index(0)
-        };
+            for job in order.iter().rev() {
+                let tail = self
+                    .forward_successors(*job)
+                    .map(|successor| (lag(*job, successor) + distances[&successor].0, successor))
+                    .max();
 
-        algo::all_simple_paths::<Vec<_>, _>(&self.graph, from, to, 0, None)
-            .map(|path| {
-                let weight = path.iter().fold(0_usize, |acc, node_index| {
-                    if let Some(weight) = self.durations.get(node_index) {
-                        acc + (*weight) as usize
+                distances.insert(
+                    *job,
+                    match tail {
+                        Some((tail, successor)) if tail >= duration(job) => (tail, Some(successor)),
+                        _ => (duration(job), None),
+                    },
+                );
+            }
+        }
+
+        // Ties are broken towards the supersink, or the supersource if reversed
+        let (mut job, (length, _)) = distances
+            .iter()
+            .map(|(job, distance)| (*job, *distance))
+            .max_by_key(|(job, (length, _))| {
+                (
+                    *length,
+                    if reversed {
+                        -(*job as i64)
                     } else {
-                        acc
-                    }
-                });
+                        *job as i64
+                    },
+                )
+            })?;
+
+        let mut path = vec![job];
+        while let Some(next) = distances[&job].1 {
+            path.push(next);
+            job = next;
+        }
+        path.reverse();
+
+        Some((length.max(0) as usize, path))
+    }
+
+    /// Computes the earliest and latest start and finish times as well as the floats of all
+    /// jobs, considering only the precedence relations.
+    ///
+    /// Latest times are computed against the given horizon, e.g. `psp.horizon`, or the
+    /// critical path length if there is none. Horizons shorter than the critical path
+    /// are extended to it.
+    pub fn compute_cpm_table(&self, horizon: Option<usize>) -> CpmTable {
+        let order = self.topological_order();
+        let duration = |job: &JobId| self.duration(*job).unwrap_or(0) as i64;
+        let lag = |from: JobId, to: JobId| {
+            self.time_lag(from, to)
+                .map(i64::from)
+                .unwrap_or_else(|| duration(&from))
+        };
+
+        let mut earliest_starts: HashMap<JobId, i64> = HashMap::new();
+        for job in &order {
+            let start = *earliest_starts.entry(*job).or_default();
+
+            for successor in self.forward_successors(*job) {
+                let successor_start = earliest_starts.entry(successor).or_default();
+                *successor_start = (*successor_start).max(start + lag(*job, successor));
+            }
+        }
+
+        let critical_path_length = order
+            .iter()
+            .map(|job| earliest_starts[job] + duration(job))
+            .max()
+            .unwrap_or(0);
+        let horizon = (horizon.unwrap_or(0) as i64).max(critical_path_length);
 
-                (weight, path)
+        let mut latest_starts: HashMap<JobId, i64> = HashMap::new();
+        let mut free_floats: HashMap<JobId, i64> = HashMap::new();
+        for job in order.iter().rev() {
+            let earliest_start = earliest_starts[job];
+            let mut latest_start = horizon - duration(job);
+            let mut free_float = horizon - earliest_start - duration(job);
+
+            for successor in self.forward_successors(*job) {
+                latest_start = latest_start.min(latest_starts[&successor] - lag(*job, successor));
+                free_float = free_float
+                    .min(earliest_starts[&successor] - earliest_start - lag(*job, successor));
+            }
+
+            latest_starts.insert(*job, latest_start);
+            free_floats.insert(*job, free_float);
+        }
+
+        let mut jobs: Vec<JobTiming> = order
+            .iter()
+            .map(|job| {
+                let earliest_start = earliest_starts[job];
+                let latest_start = latest_starts[job];
+
+                JobTiming {
+                    job: *job,
+                    earliest_start: earliest_start as usize,
+                    earliest_finish: (earliest_start + duration(job)) as usize,
+                    latest_start: latest_start as usize,
+                    latest_finish: (latest_start + duration(job)) as usize,
+                    total_float: (latest_start - earliest_start) as usize,
+                    free_float: free_floats[job].max(0) as usize,
+                }
             })
-            .max_by_key(|path| path.0)
+            .collect();
+        jobs.sort_unstable_by_key(|timing| timing.job);
+
+        CpmTable {
+            horizon: horizon as usize,
+            jobs,
+        }
     }
 
-    /// Based on the number of edges
-    pub fn find_longest_path(&self) -> Option<Vec<NodeId>> {
-        algo::all_simple_paths::<Vec<_>, _>(
-            &self.graph,
-            self.graph.from_index(0),
-            self.graph.from_index(self.psp.jobs - 1),
-            0,
-            None,
-        )
-        .map(|path| (path.len(), path))
-        .max_by_key(|path| path.0)
-        .map(|(_, path)| path)
+    /// Based on the number of edges, leading from the supersource to the supersink
+    pub fn find_longest_path(&self) -> Option<Vec<JobId>> {
+        let mut edges: HashMap<JobId, (usize, Option<JobId>)> = HashMap::new();
+
+        for job in self.topological_order() {
+            let (count, _) = *edges.entry(job).or_insert((0, None));
+
+            for successor in self.forward_successors(job) {
+                let entry = edges.entry(successor).or_insert((0, None));
+                if count + 1 > entry.0 {
+                    *entry = (count + 1, Some(job));
+                }
+            }
+        }
+
+        let (mut job, _) = edges
+            .iter()
+            .max_by_key(|(job, (count, _))| (*count, **job))
+            .map(|(job, distance)| (*job, *distance))?;
+
+        let mut path = vec![job];
+        while let Some(predecessor) = edges[&job].1 {
+            path.push(predecessor);
+            job = predecessor;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// All jobs that can only start after the job started, by following arcs of
+    /// non-negative time lags
+    pub fn transitive_successors(&self, job: JobId) -> HashSet<JobId> {
+        self.closure
+            .get(job as usize)
+            .map(|reachable| reachable.ones().map(|job| job as JobId).collect())
+            .unwrap_or_default()
+    }
+
+    /// Successors of a job, excluding arcs of maximal time lags
+    fn forward_successors(&self, job: JobId) -> impl Iterator<Item = JobId> + '_ {
+        self.successors
+            .get(job as usize)
+            .into_iter()
+            .flatten()
+            .filter(|(_, time_lag)| *time_lag >= 0)
+            .map(|(successor, _)| *successor)
+    }
+
+    /// All jobs ordered such that every job precedes its forward successors
+    fn topological_order(&self) -> Vec<JobId> {
+        // Counted from the arcs of the network, where arcs listed twice are merged, as
+        // the counts are decreased along those arcs as well
+        let mut predecessor_counts: HashMap<JobId, usize> = (1..self.durations.len() as JobId)
+            .map(|job| (job, 0))
+            .collect();
+        for job in 1..self.durations.len() as JobId {
+            for successor in self.forward_successors(job) {
+                *predecessor_counts.entry(successor).or_default() += 1;
+            }
+        }
+
+        let mut ready: Vec<JobId> = predecessor_counts
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(job, _)| *job)
+            .collect();
+        ready.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut order = Vec::with_capacity(predecessor_counts.len());
+        while let Some(job) = ready.pop() {
+            order.push(job);
+
+            for successor in self.forward_successors(job) {
+                let count = predecessor_counts.get_mut(&successor).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(successor);
+                }
+            }
+        }
+        debug_assert_eq!(order.len(), predecessor_counts.len());
+
+        order
     }
 
     /// Returns vector of job number execution ranks.
     ///
     /// Warning: those ranks are not node ids but job ids
-    pub fn compute_job_execution_ranks(&self) -> Vec<Vec<u8>> {
+    pub fn compute_job_execution_ranks(&self) -> Vec<Vec<JobId>> {
         let mut successor_map = {
             let mut map = HashMap::new();
             for node in &self.psp.precedence_relations {
-                map.insert(node.job_number, node.successors.clone());
+                map.insert(
+                    node.job_number,
+                    node.forward_successors().copied().collect::<Vec<JobId>>(),
+                );
             }
             map
         };
 
-        let prerequisite_map: HashMap<u8, Vec<u8>> = {
-            let mut map: HashMap<u8, Vec<u8>> = HashMap::new();
+        let prerequisite_map: HashMap<JobId, Vec<JobId>> = {
+            let mut map: HashMap<JobId, Vec<JobId>> = HashMap::new();
             for (job_number, successors) in successor_map.iter() {
                 for successor in successors {
                     if let Some(requirements) = map.get_mut(successor) {
@@ -154,12 +597,20 @@ impl DAG {
             map
         };
 
-        let mut ranks: Vec<Vec<u8>> = vec![];
+        let mut ranks: Vec<Vec<JobId>> = vec![];
 
-        // Initially get all successors from the first node
+        // Initially get all successors from the first node, followed by the sources of
+        // projects that are not connected to the supersource
         let mut same_rank = successor_map.remove(&1).unwrap_or_default();
+        let mut unconnected_sources: Vec<JobId> = successor_map
+            .keys()
+            .filter(|job| !prerequisite_map.contains_key(*job) && !same_rank.contains(job))
+            .copied()
+            .collect();
+        unconnected_sources.sort_unstable();
+        same_rank.append(&mut unconnected_sources);
 
-        let mut visited_nodes: HashSet<u8> = HashSet::new();
+        let mut visited_nodes: HashSet<JobId> = HashSet::from([1]);
         loop {
             if same_rank.is_empty() {
                 break;
@@ -170,7 +621,7 @@ impl DAG {
             }
 
             // Expand all successors of current same_level nodes
-            let successors: Vec<Vec<u8>> = same_rank
+            let successors: Vec<Vec<JobId>> = same_rank
                 .iter()
                 .map(|current_job| {
                     successor_map
@@ -193,7 +644,7 @@ impl DAG {
             ranks.push(same_rank);
 
             // Replace same_level with next successors
-            same_rank = successors.into_iter().flatten().collect::<Vec<u8>>();
+            same_rank = successors.into_iter().flatten().collect::<Vec<JobId>>();
             same_rank.sort_unstable();
             same_rank.dedup();
         }
@@ -201,69 +652,40 @@ impl DAG {
         ranks
     }
 
-    pub fn filtered_reduced_neighborhood(&self, schedule: &[u8]) -> Vec<&(usize, usize)> {
+    pub fn filtered_reduced_neighborhood(&self, schedule: &[JobId]) -> Vec<&(usize, usize)> {
         self.reduced_neighborhood
             .iter()
-            .filter(|(u, v)| {
-                for x in *u..*v {
-                    if self.graph.has_edge(
-                        self.job_to_nodes[&schedule[*u]],
-                        self.job_to_nodes[&schedule[x + 1]],
-                    ) || self.graph.has_edge(
-                        self.job_to_nodes[&schedule[x]],
-                        self.job_to_nodes[&schedule[*v]],
-                    ) {
-                        return false;
-                    }
-                }
-                true
-            })
-            //.map(|(a,b)| (*a,*b))
+            .filter(|(u, v)| self.is_feasible_swap(schedule, *u, *v))
             .collect()
     }
 
     pub fn compute_reduced_neighborhood_moves(
         &self,
-        schedule: &[u8],
+        schedule: &[JobId],
         swap_range: usize,
-    ) -> Vec<(u8, u8)> {
+    ) -> Vec<(JobId, JobId)> {
+        let positions = self.list_positions(schedule);
+
         // Filter out infeasible moves, i.e. moves that violate a precedence relation
-        let filter_op = |(u, v): &(u8, u8)| {
-            // Check paths not existing from u to v and check paths not existing from u,
-            // u+1, u+2, ..., v-1 to v
-            let index_u = schedule.iter().position(|&node| node == *u);
-            let index_v = schedule.iter().position(|&node| node == *v);
-
-            if let Some(index_u) = index_u {
-                if let Some(index_v) = index_v {
-                    let start_index = index_u.min(index_v);
-                    let end_index = index_v.max(index_u);
-
-                    let nodes_between = &schedule[start_index..end_index + 1];
-
-                    return nodes_between.iter().all(|node| {
-                        !(self.graph.has_edge(
-                            self.job_to_nodes[node],
-                            self.job_to_nodes[nodes_between.last().unwrap()],
-                        ) || self.graph.has_edge(
-                            self.job_to_nodes[nodes_between.first().unwrap()],
-                            self.job_to_nodes[node],
-                        ))
-                    });
+        let filter_op = |(u, v): &(JobId, JobId)| {
+            let position = |job: &JobId| positions.get(*job as usize).copied().flatten();
+
+            match (position(u), position(v)) {
+                (Some(index_u), Some(index_v)) => {
+                    self.is_feasible_swap(schedule, index_u.min(index_v), index_u.max(index_v))
                 }
+                _ => false,
             }
-
-            false
         };
 
         // Reduced neighborhood to initial solution depends on the neighborhood size
         // parameter (swap range) and is an upper bound for move generation
-        let windows: Vec<&[u8]> = schedule.windows(swap_range).collect();
+        let windows: Vec<&[JobId]> = schedule.windows(swap_range).collect();
 
         if let Some((&last_window, windows)) = windows.split_last() {
-            let all_moves = windows.iter().map_while(|&window: &&[u8]| {
+            let all_moves = windows.iter().map_while(|&window: &&[JobId]| {
                 if let Some(first) = window.first() {
-                    let neighbors: Vec<(u8, u8)> = window
+                    let neighbors: Vec<(JobId, JobId)> = window
                         .iter()
                         .skip(1)
                         .map(|neighbor| (*first.min(neighbor), *neighbor.max(first)))
@@ -281,7 +703,7 @@ impl DAG {
 
             let last_window_moves = last_window.windows(swap_range).map_while(|window| {
                 if let Some(first) = window.first() {
-                    let neighbors: Vec<(u8, u8)> = window
+                    let neighbors: Vec<(JobId, JobId)> = window
                         .iter()
                         .skip(1)
                         .filter(|&neighbor| *neighbor != 0)
@@ -301,124 +723,1229 @@ impl DAG {
         }
     }
 
-    pub fn compute_execution_time(&self, schedule: &[u8], swap: Option<(u8, u8)>) -> usize {
-        let mut resources: Vec<Vec<u32>> = vec![vec![0; self.compute_upper_bound()]; 4];
-        let resource_limits = vec![
-            self.psp.resource_availabilities.r1,
-            self.psp.resource_availabilities.r2,
-            self.psp.resource_availabilities.r3,
-            self.psp.resource_availabilities.r4,
-        ];
+    /// Position of every listed job in an activity list, by job number
+    fn list_positions(&self, schedule: &[JobId]) -> Vec<Option<usize>> {
+        let mut positions = vec![None; self.durations.len()];
+        for (position, job) in schedule.iter().enumerate() {
+            if let Some(slot) = positions.get_mut(*job as usize) {
+                *slot = Some(position);
+            }
+        }
 
-        // Mapping of job number --> earliest job start time
-        let mut start_times: HashMap<u8, usize> = HashMap::new();
+        positions
+    }
 
-        // Insert the genesis task with a start time of 0
-        start_times.insert(1, 0);
+    /// Whether swapping the jobs at two positions of an activity list keeps every job
+    /// behind the jobs preceding it, also through paths of several arcs.
+    ///
+    /// The first job must not precede any job up to the second one, which must not be
+    /// preceded by any job from the first one on.
+    fn is_feasible_swap(&self, schedule: &[JobId], first: usize, last: usize) -> bool {
+        let (before, after) = (schedule[first], schedule[last]);
 
-        // Compute earliest start time for each task
-        // The earliest start time for a job is: maximum(start time of all it's predecessors + their execution time)
+        schedule[first + 1..=last]
+            .iter()
+            .all(|job| !self.precedes(before, *job))
+            && schedule[first..last]
+                .iter()
+                .all(|job| !self.precedes(*job, after))
+    }
 
-        for job_id in schedule {
-            let job_id = &if let Some((i, j)) = swap {
-                if *job_id == i {
-                    j
-                } else if *job_id == j {
-                    i
+    /// Finds a job that is listed behind one of its (transitive) successors, if any.
+    ///
+    /// Decoders treat predecessors listed later as if they started at time 0, thus
+    /// only activity lists without violations are decoded into feasible schedules.
+    pub fn find_precedence_violation(&self, schedule: &[JobId]) -> Option<PrecedenceViolation> {
+        let mut listed = FixedBitSet::with_capacity(self.durations.len());
+
+        for job in schedule {
+            let Some(reachable) = self.closure.get(*job as usize) else {
+                continue;
+            };
+
+            if let Some(successor) = reachable.intersection(&listed).next() {
+                return Some(PrecedenceViolation {
+                    predecessor: *job,
+                    successor: successor as JobId,
+                });
+            }
+            listed.insert(*job as usize);
+        }
+
+        None
+    }
+
+    /// Whether every job of the activity list is listed behind the jobs preceding it
+    pub fn is_precedence_feasible(&self, schedule: &[JobId]) -> bool {
+        self.find_precedence_violation(schedule).is_none()
+    }
+
+    /// Reorders an activity list into the precedence feasible list closest to it.
+    ///
+    /// Jobs are taken in list order, except that a job is postponed until all listed
+    /// jobs preceding it have been taken. Feasible lists are returned unchanged.
+    pub fn repair_activity_list(&self, schedule: &[JobId]) -> Vec<JobId> {
+        let mut listed = FixedBitSet::with_capacity(self.durations.len());
+        let mut positions = vec![0; self.durations.len()];
+        for (position, job) in schedule.iter().enumerate() {
+            if (*job as usize) < self.durations.len() {
+                listed.insert(*job as usize);
+                positions[*job as usize] = position;
+            }
+        }
+
+        let listed_successors = |job: JobId| {
+            self.closure
+                .get(job as usize)
+                .into_iter()
+                .flat_map(|reachable| reachable.intersection(&listed))
+        };
+
+        let mut predecessor_counts = vec![0_usize; self.durations.len()];
+        for job in schedule {
+            for successor in listed_successors(*job) {
+                predecessor_counts[successor] += 1;
+            }
+        }
+
+        // Positions of the jobs whose listed predecessors have all been taken
+        let mut ready: BinaryHeap<Reverse<usize>> = schedule
+            .iter()
+            .enumerate()
+            .filter(|(_, job)| {
+                predecessor_counts
+                    .get(**job as usize)
+                    .is_none_or(|count| *count == 0)
+            })
+            .map(|(position, _)| Reverse(position))
+            .collect();
+
+        let mut taken = vec![false; schedule.len()];
+        let mut repaired = Vec::with_capacity(schedule.len());
+        while let Some(Reverse(position)) = ready.pop() {
+            let job = schedule[position];
+            taken[position] = true;
+            repaired.push(job);
+
+            for successor in listed_successors(job) {
+                predecessor_counts[successor] -= 1;
+                if predecessor_counts[successor] == 0 {
+                    ready.push(Reverse(positions[successor]));
+                }
+            }
+        }
+
+        // Jobs on cycles of arcs cannot be ordered, they keep their relative order
+        repaired.extend(
+            schedule
+                .iter()
+                .zip(taken)
+                .filter(|(_, taken)| !taken)
+                .map(|(job, _)| *job),
+        );
+
+        repaired
+    }
+
+    pub fn compute_execution_time(
+        &self,
+        schedule: &[JobId],
+        swap: Option<(JobId, JobId)>,
+    ) -> usize {
+        // Mode assignments exceeding a resource budget are rejected
+        if !self.feasible_modes {
+            return usize::MAX;
+        }
+
+        let (start_times, _) = self.compute_start_times(schedule, swap);
+
+        self.makespan(&start_times)
+    }
+
+    /// Decodes a schedule into the start and finish time of every job
+    pub fn compute_solution(&self, schedule: &[JobId]) -> Solution {
+        let (start_times, resources) = self.compute_start_times(schedule, None);
+
+        Solution::new(
+            start_times
+                .into_iter()
+                .map(|(job, start)| ScheduledJob {
+                    job,
+                    mode: self.modes.get(&job).copied().unwrap_or(1),
+                    start,
+                    finish: start + self.duration(job).unwrap_or(0) as usize,
+                })
+                .collect(),
+            resources.iter().map(ResourceProfile::to_periods).collect(),
+        )
+    }
+
+    /// Evaluates a schedule under the objective set by [`DAG::with_objective`]
+    pub fn compute_objective(&self, schedule: &[JobId], swap: Option<(JobId, JobId)>) -> usize {
+        if !self.feasible_modes {
+            return usize::MAX;
+        }
+
+        let (start_times, _) = self.compute_start_times(schedule, swap);
+
+        self.evaluate(&start_times)
+    }
+
+    /// Value of the configured objective for the given start times
+    fn evaluate(&self, start_times: &HashMap<JobId, usize>) -> usize {
+        match self.objective {
+            Objective::Makespan => self.makespan(start_times),
+            Objective::TotalWeightedTardiness => self
+                .project_outcomes(start_times)
+                .iter()
+                .map(|outcome| outcome.weighted_tardiness)
+                .sum(),
+            Objective::TotalProjectDelay => self
+                .project_outcomes(start_times)
+                .iter()
+                .map(|outcome| outcome.delay)
+                .sum(),
+        }
+    }
+
+    /// Computes completion, tardiness and delay of every project of the instance
+    pub fn compute_project_outcomes(&self, schedule: &[JobId]) -> Vec<ProjectOutcome> {
+        let (start_times, _) = self.compute_start_times(schedule, None);
+
+        self.project_outcomes(&start_times)
+    }
+
+    fn project_outcomes(&self, start_times: &HashMap<JobId, usize>) -> Vec<ProjectOutcome> {
+        self.psp
+            .project_jobs()
+            .into_iter()
+            .map(|(info, jobs)| {
+                let completion = jobs
+                    .filter_map(|job| {
+                        let start_time = start_times.get(&job)?;
+
+                        Some(start_time + self.duration(job)? as usize)
+                    })
+                    .max()
+                    .unwrap_or(info.relative_date as usize);
+
+                ProjectOutcome::new(info, completion)
+            })
+            .collect()
+    }
+
+    /// Finish time of the last job
+    fn makespan(&self, start_times: &HashMap<JobId, usize>) -> usize {
+        start_times
+            .iter()
+            .map(|(job, start)| start + self.duration(*job).unwrap_or(0) as usize)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Decodes a schedule with the configured schedule generation scheme.
+    ///
+    /// Returns the start time of every job and the usage profile of every renewable and
+    /// doubly constrained resource.
+    fn compute_start_times(
+        &self,
+        schedule: &[JobId],
+        swap: Option<(JobId, JobId)>,
+    ) -> (HashMap<JobId, usize>, Vec<ResourceProfile>) {
+        match self.decoder {
+            Decoder::Serial => self.serial_start_times(schedule, swap),
+            Decoder::Parallel => self.parallel_start_times(schedule, swap),
+            Decoder::BestOf => {
+                let serial = self.serial_start_times(schedule, swap);
+                let parallel = self.parallel_start_times(schedule, swap);
+
+                if self.evaluate(&parallel.0) < self.evaluate(&serial.0) {
+                    parallel
                 } else {
-                    *job_id
+                    serial
                 }
-            } else {
-                *job_id
-            };
+            }
+        }
+    }
 
-            let predecessors_node_ids = self.graph.neighbors_directed(
-                *self.job_to_nodes.get(job_id).unwrap(),
-                petgraph::EdgeDirection::Incoming,
-            );
+    /// Decodes a schedule with the parallel (time incrementing) schedule generation scheme
+    fn parallel_start_times(
+        &self,
+        schedule: &[JobId],
+        swap: Option<(JobId, JobId)>,
+    ) -> (HashMap<JobId, usize>, Vec<ResourceProfile>) {
+        let swapped: Vec<JobId> = schedule
+            .iter()
+            .map(|job_id| Self::swapped(*job_id, swap))
+            .collect();
+        let positions = self.list_positions(&swapped);
+        let position = |job_id: JobId| positions.get(job_id as usize).copied().flatten();
+
+        let mut state = self.initial_parallel_state(&swapped, position);
+        while !state.eligible.is_empty() {
+            self.decode_decision_point(&mut state, position, |_| {});
+        }
+
+        trace!("schedule: {schedule:?}");
+        let start_times = state.decoding.start_time_map();
+        trace!("start_times: {start_times:?}");
 
-            let start_time = predecessors_node_ids
-                .map(|node_id| *self.node_to_jobs.get(&node_id).unwrap())
-                .map(|job_number| {
-                    let duration = self
-                        .durations
-                        .get(self.job_to_nodes.get(&job_number).unwrap())
-                        .copied()
-                        .unwrap_or(0) as usize;
+        (start_times, state.decoding.resources)
+    }
+
+    fn initial_parallel_state(
+        &self,
+        schedule: &[JobId],
+        position: impl Fn(JobId) -> Option<usize>,
+    ) -> ParallelState {
+        let decoding = self.initial_decoding_state();
 
-                    *start_times.get(&job_number).unwrap_or(&0) + duration
+        let mut waiting = vec![0; self.durations.len()];
+        for job_id in schedule {
+            waiting[*job_id as usize] = self.predecessors[*job_id as usize]
+                .iter()
+                .filter(|(predecessor, _)| {
+                    decoding.start_times[*predecessor as usize].is_none()
+                        && Self::listed_before(&position, *predecessor, *job_id)
                 })
-                .max();
-
-            if let Some(start_time) = start_time {
-                let mut start_time = start_time;
-
-                // Once the earliest start time has been determined, try fitting the task into the resources vector
-                if let Some(requirements) = self.requests.get(job_id) {
-                    // (0..4).into_iter();
-
-                    // (1) For each index in 0..4:
-                    // (2) - check if: resources[index][start_time] + requirements[index] <= resource_limits[index]
-                    // (3) - if true:
-                    // (4) --> for d in 0..self.durations[job_id]:
-                    // (5)       - check if resources[index][start_time + d] + requirements[index] <= resource_limits[index]
-                    // (6)       - if true: continue
-                    // (7)       - else: start_time += 1 --> repeat (1)
-                    // (8)     - loop (4) finishes, continue loop (2) iteration
-                    // (9) - loop (2) finishes --> finish
-                    // (4) - else --> start_time += 1 --> repeat (1)
-
-                    loop {
-                        let mut finished = true;
-
-                        'index_loop: for index in 0..4 {
-                            for duration in 0..self.durations[&self.job_to_nodes[job_id]] {
-                                if resources[index][start_time + duration as usize]
-                                    + (requirements[index] as u32)
-                                    > resource_limits[index] as u32
-                                {
-                                    start_time += 1;
-                                    finished = false;
-                                    break 'index_loop;
-                                }
-                            }
-                        }
-
-                        if finished {
-                            // Put task resource requirements into resources vector
-                            for index in 0..4 {
-                                for duration in 0..self.durations[&self.job_to_nodes[job_id]] {
-                                    resources[index][start_time + duration as usize] +=
-                                        requirements[index] as u32;
-                                }
-                            }
+                .count();
+        }
 
-                            break;
-                        }
-                    }
+        ParallelState {
+            eligible: schedule
+                .iter()
+                .filter(|job_id| waiting[**job_id as usize] == 0)
+                .copied()
+                .collect(),
+            decoding,
+            time: 0,
+            waiting,
+            finish_times: BinaryHeap::new(),
+        }
+    }
+
+    /// Whether both jobs are listed and `before` is listed first
+    fn listed_before(
+        position: impl Fn(JobId) -> Option<usize>,
+        before: JobId,
+        after: JobId,
+    ) -> bool {
+        matches!(
+            (position(before), position(after)),
+            (Some(before), Some(after)) if before < after
+        )
+    }
+
+    /// Starts the eligible jobs in list order if their predecessors allow it and the
+    /// resources suffice at the current decision point. Afterwards, the decision point
+    /// advances to the next finish time or earliest start of an eligible job.
+    ///
+    /// `position` is the list position of a job, `on_eligible` is called for every job
+    /// whose predecessors allow it to start at the current decision point.
+    fn decode_decision_point(
+        &self,
+        state: &mut ParallelState,
+        position: impl Fn(JobId) -> Option<usize>,
+        mut on_eligible: impl FnMut(JobId),
+    ) {
+        let limited_resources = &self.limited_resources;
+        let resource_limits = &self.psp.resource_availabilities.resources;
+        let ParallelState {
+            decoding:
+                DecodingState {
+                    start_times,
+                    resources,
+                },
+            time,
+            eligible,
+            waiting,
+            finish_times,
+        } = state;
+        let now = *time;
+        let mut next_time = usize::MAX;
+
+        let mut index = 0;
+        while index < eligible.len() {
+            let job_id = eligible[index];
+            let duration = self.durations[job_id as usize] as usize;
+
+            // Predecessors listed later are only respected as far as they hold by
+            // themselves, as in the serial scheme
+            let earliest_start = self.predecessors[job_id as usize]
+                .iter()
+                .filter_map(|(predecessor, time_lag)| {
+                    let predecessor_start = start_times[*predecessor as usize]? as i64;
+
+                    Some((predecessor_start + *time_lag as i64).max(0) as usize)
+                })
+                .max()
+                .unwrap_or(0)
+                .max(self.release_dates.get(&job_id).copied().unwrap_or(0));
+            if earliest_start > now {
+                next_time = next_time.min(earliest_start);
+                index += 1;
+                continue;
+            }
+            on_eligible(job_id);
+
+            // No job starts after the decision point, thus the usage does not increase
+            // afterwards and a job fits if it fits at the decision point. Demands beyond
+            // the capacity are rejected by `DAG::new`, like the serial scheme, the job is
+            // started regardless of them instead of waiting forever.
+            let requirements = &self.requests[job_id as usize];
+            let fits = duration == 0
+                || limited_resources.iter().enumerate().all(|(slot, &index)| {
+                    let (demand, capacity) = (requirements[index], resource_limits[index]);
+
+                    demand == 0
+                        || demand > capacity
+                        || resources[slot].usage_at(now) + demand <= capacity
+                });
+            if !fits {
+                index += 1;
+                continue;
+            }
+
+            for (slot, &index) in limited_resources.iter().enumerate() {
+                resources[slot].add(now, duration, requirements[index]);
+            }
+            let restarted = start_times[job_id as usize].replace(now).is_some();
+            finish_times.push(Reverse(now + duration));
+            eligible.remove(index);
+            if restarted {
+                continue;
+            }
+
+            // Successors listed later become eligible once all their predecessors listed
+            // earlier started, and are decided on at this decision point as well
+            for (successor, _) in &self.successors[job_id as usize] {
+                if !Self::listed_before(&position, job_id, *successor) {
+                    continue;
                 }
 
-                start_times.insert(*job_id, start_time);
+                waiting[*successor as usize] -= 1;
+                if waiting[*successor as usize] == 0 {
+                    let successor_position = position(*successor);
+                    let slot =
+                        eligible.partition_point(|job_id| position(*job_id) < successor_position);
+                    eligible.insert(slot, *successor);
+                }
             }
         }
 
+        // Once all started jobs finished, the first eligible job fits, thus there always
+        // is a further event
+        while finish_times
+            .peek()
+            .is_some_and(|Reverse(finish)| *finish <= now)
+        {
+            finish_times.pop();
+        }
+        *time = finish_times
+            .peek()
+            .map_or(usize::MAX, |Reverse(finish)| *finish)
+            .min(next_time);
+        assert!(
+            eligible.is_empty() || *time < usize::MAX,
+            "parallel decoding stalled at time {now} with eligible jobs {eligible:?}"
+        );
+    }
+
+    /// The job at a position of the activity list after swapping two jobs
+    fn swapped(job_id: JobId, swap: Option<(JobId, JobId)>) -> JobId {
+        match swap {
+            Some((i, j)) if job_id == i => j,
+            Some((i, j)) if job_id == j => i,
+            _ => job_id,
+        }
+    }
+
+    /// Decodes a schedule with the serial schedule generation scheme
+    fn serial_start_times(
+        &self,
+        schedule: &[JobId],
+        swap: Option<(JobId, JobId)>,
+    ) -> (HashMap<JobId, usize>, Vec<ResourceProfile>) {
+        let mut state = self.initial_decoding_state();
+        for job_id in schedule {
+            self.decode_serially(&mut state, Self::swapped(*job_id, swap));
+        }
+
         trace!("schedule: {schedule:?}");
+        let start_times = state.start_time_map();
         trace!("start_times: {start_times:?}");
-        trace!("resources[0]: {:?}", resources[0]);
-        trace!("resources[1]: {:?}", resources[1]);
-        trace!("resources[2]: {:?}", resources[2]);
-        trace!("resources[3]: {:?}", resources[3]);
-
-        // Zip resource usage vectors together and filter out all unused space time slots
-        let mut resources = resources;
-        resources
-            .remove(0)
+        for (index, usage) in state.resources.iter().enumerate() {
+            trace!("resources[{index}]: {usage:?}");
+        }
+
+        (start_times, state.resources)
+    }
+
+    fn initial_decoding_state(&self) -> DecodingState {
+        // Renewable and doubly constrained resources are limited per time slot, the
+        // budgets of the latter are kept by the mode assignment
+        let mut start_times = vec![None; self.durations.len()];
+
+        // Insert the genesis task with a start time of 0
+        start_times[1] = Some(0);
+
+        DecodingState {
+            start_times,
+            resources: vec![ResourceProfile::new(); self.limited_resources.len()],
+        }
+    }
+
+    /// Starts a job as early as its predecessors and the resources allow
+    fn decode_serially(&self, state: &mut DecodingState, job_id: JobId) {
+        let resource_limits = &self.psp.resource_availabilities.resources;
+
+        // The earliest start time for a job is: maximum(start time of all it's predecessors + their execution time)
+        // Predecessors are respected with their start-to-start time lag. Maximal time
+        // lags (negative arcs) are only satisfied as far as they hold by themselves.
+        // Jobs of a project never start before its release date.
+        let release_date = self.release_dates.get(&job_id).copied().unwrap_or(0);
+        let mut start_time = self.predecessors[job_id as usize]
+            .iter()
+            .map(|(predecessor, time_lag)| {
+                let predecessor_start =
+                    state.start_times[*predecessor as usize].unwrap_or(0) as i64;
+
+                (predecessor_start + *time_lag as i64).max(0) as usize
+            })
+            .max()
+            .unwrap_or(0)
+            .max(release_date);
+
+        // Once the earliest start time has been determined, the job is postponed until
+        // every resource fits its demand over its whole duration
+        let requirements = &self.requests[job_id as usize];
+        let duration = self.durations[job_id as usize] as usize;
+        let resources = &mut state.resources;
+
+        loop {
+            let fitting_start = self
+                .limited_resources
+                .iter()
+                .enumerate()
+                .map(|(slot, &index)| {
+                    // Demands beyond the capacity are rejected by `DAG::new`
+                    resources[slot]
+                        .earliest_start(
+                            start_time,
+                            duration,
+                            requirements[index],
+                            resource_limits[index],
+                        )
+                        .unwrap_or(start_time)
+                })
+                .max()
+                .unwrap_or(start_time);
+
+            if fitting_start == start_time {
+                break;
+            }
+            start_time = fitting_start;
+        }
+
+        for (slot, &index) in self.limited_resources.iter().enumerate() {
+            resources[slot].add(start_time, duration, requirements[index]);
+        }
+
+        state.start_times[job_id as usize] = Some(start_time);
+    }
+
+    /// Decodes a schedule, keeping the decoding state in front of every position (serial
+    /// decoder) or decision point (parallel decoder), so that swaps can be evaluated by
+    /// [`DAG::compute_objective_incremental`].
+    ///
+    /// Swaps evaluated with [`Decoder::BestOf`] decode the whole schedule.
+    pub fn prepare_incremental_evaluation(&self, schedule: &[JobId]) -> IncrementalEvaluation {
+        let positions = self.list_positions(schedule);
+
+        let (snapshots, start_times) = match self.decoder {
+            Decoder::Serial => {
+                let mut state = self.initial_decoding_state();
+                let mut states = Vec::with_capacity(schedule.len() + 1);
+
+                for job_id in schedule {
+                    states.push(state.clone());
+                    self.decode_serially(&mut state, *job_id);
+                }
+                let start_times = state.start_time_map();
+                states.push(state);
+
+                (Snapshots::Serial(states), Some(start_times))
+            }
+            Decoder::Parallel => {
+                let position = |job_id: JobId| positions.get(job_id as usize).copied().flatten();
+                let mut state = self.initial_parallel_state(schedule, position);
+                let mut states = vec![];
+                let mut first_eligible = vec![usize::MAX; self.durations.len()];
+
+                while !state.eligible.is_empty() {
+                    let decision_point = states.len();
+                    states.push(state.clone());
+                    self.decode_decision_point(&mut state, position, |job_id| {
+                        let first = &mut first_eligible[job_id as usize];
+                        *first = (*first).min(decision_point);
+                    });
+                }
+
+                (
+                    Snapshots::Parallel {
+                        states,
+                        first_eligible,
+                    },
+                    Some(state.decoding.start_time_map()),
+                )
+            }
+            Decoder::BestOf => (Snapshots::None, None),
+        };
+
+        let value = match start_times {
+            Some(start_times) if self.feasible_modes => self.evaluate(&start_times),
+            _ => self.compute_objective(schedule, None),
+        };
+
+        IncrementalEvaluation {
+            schedule: schedule.to_vec(),
+            positions,
+            snapshots,
+            value,
+        }
+    }
+
+    /// Evaluates a swap of two jobs like [`DAG::compute_objective`], decoding only from
+    /// the first swapped position (serial decoder) or from the first decision point at
+    /// which a swapped job could start (parallel decoder) onwards.
+    pub fn compute_objective_incremental(
+        &self,
+        evaluation: &IncrementalEvaluation,
+        swap: (JobId, JobId),
+    ) -> usize {
+        if !self.feasible_modes {
+            return usize::MAX;
+        }
+
+        let position = |job_id: JobId| evaluation.positions.get(job_id as usize).copied().flatten();
+        let (Some(position_a), Some(position_b)) = (position(swap.0), position(swap.1)) else {
+            return self.compute_objective(&evaluation.schedule, Some(swap));
+        };
+        let first = position_a.min(position_b);
+        let last = position_a.max(position_b);
+
+        match &evaluation.snapshots {
+            Snapshots::Serial(states) => {
+                self.serial_swap_objective(evaluation, states, swap, first, last)
+            }
+            Snapshots::Parallel {
+                states,
+                first_eligible,
+            } => {
+                self.parallel_swap_objective(evaluation, states, first_eligible, swap, first, last)
+            }
+            Snapshots::None => self.compute_objective(&evaluation.schedule, Some(swap)),
+        }
+    }
+
+    /// If both swapped jobs and all jobs between them start as before, the remaining
+    /// jobs do as well, and the known objective value is returned right away.
+    fn serial_swap_objective(
+        &self,
+        evaluation: &IncrementalEvaluation,
+        states: &[DecodingState],
+        swap: (JobId, JobId),
+        first: usize,
+        last: usize,
+    ) -> usize {
+        let mut state = states[first].clone();
+        for job_id in &evaluation.schedule[first..=last] {
+            self.decode_serially(&mut state, Self::swapped(*job_id, Some(swap)));
+        }
+
+        let unchanged_state = &states[last + 1];
+        if evaluation.schedule[first..=last].iter().all(|job_id| {
+            state.start_times[*job_id as usize] == unchanged_state.start_times[*job_id as usize]
+        }) {
+            return evaluation.value;
+        }
+
+        for job_id in &evaluation.schedule[last + 1..] {
+            self.decode_serially(&mut state, *job_id);
+        }
+
+        self.evaluate(&state.start_time_map())
+    }
+
+    /// Up to the first decision point at which a swapped job could start, the order of
+    /// the two jobs does not matter, unless it decides whether a job related to one of
+    /// them by an arc waits for the other one.
+    fn parallel_swap_objective(
+        &self,
+        evaluation: &IncrementalEvaluation,
+        states: &[ParallelState],
+        first_eligible: &[usize],
+        swap: (JobId, JobId),
+        first: usize,
+        last: usize,
+    ) -> usize {
+        let schedule = &evaluation.schedule;
+        let related = |job_id: JobId, other: &JobId| {
+            self.time_lag(job_id, *other).is_some() || self.time_lag(*other, job_id).is_some()
+        };
+        let decision_point = first_eligible[swap.0 as usize].min(first_eligible[swap.1 as usize]);
+        let (Some(state), false) = (
+            states.get(decision_point),
+            schedule[first + 1..=last]
+                .iter()
+                .any(|job_id| related(schedule[first], job_id))
+                || schedule[first..last]
+                    .iter()
+                    .any(|job_id| related(schedule[last], job_id)),
+        ) else {
+            return self.compute_objective(schedule, Some(swap));
+        };
+
+        let position = |job_id| {
+            evaluation
+                .positions
+                .get(Self::swapped(job_id, Some(swap)) as usize)
+                .copied()
+                .flatten()
+        };
+        let mut state = state.clone();
+        state.eligible.sort_by_key(|job_id| position(*job_id));
+        while !state.eligible.is_empty() {
+            self.decode_decision_point(&mut state, position, |_| {});
+
+            // Once both swapped jobs started, the remaining decision points are decoded as
+            // before if all jobs started so far did so at the same times as before
+            let start_times = &state.decoding.start_times;
+            if start_times[swap.0 as usize].is_some() && start_times[swap.1 as usize].is_some() {
+                if let Ok(index) = states.binary_search_by_key(&state.time, |state| state.time) {
+                    if states[index].decoding.start_times == *start_times {
+                        return evaluation.value;
+                    }
+                }
+            }
+        }
+
+        self.evaluate(&state.decoding.start_time_map())
+    }
+}
+
+/// Adds an arc, replacing the time lag of an existing arc to the same job
+fn insert_arc(arcs: &mut Vec<Arc>, arc: Arc) {
+    match arcs.iter_mut().find(|(job, _)| *job == arc.0) {
+        Some(existing) => *existing = arc,
+        None => arcs.push(arc),
+    }
+}
+
+/// Jobs reachable from every job by arcs of non-negative time lags, indexed by job number
+fn transitive_closure(psp: &PspLibProblem, size: usize) -> Vec<FixedBitSet> {
+    let mut successors = vec![vec![]; size];
+    for relation in &psp.precedence_relations {
+        successors[relation.job_number as usize].extend(relation.forward_successors().copied());
+    }
+
+    (0..size)
+        .map(|job| {
+            let mut reachable = FixedBitSet::with_capacity(size);
+            let mut stack: Vec<JobId> = successors[job].clone();
+
+            while let Some(successor) = stack.pop() {
+                if !reachable.put(successor as usize) {
+                    stack.extend(&successors[successor as usize]);
+                }
+            }
+
+            reachable
+        })
+        .collect()
+}
+
+/// Start times and resource usage of the jobs decoded so far
+#[derive(Debug, Clone)]
+struct DecodingState {
+    /// Start time by job number, unset for jobs that have not been decoded yet
+    start_times: Vec<Option<usize>>,
+    resources: Vec<ResourceProfile>,
+}
+
+impl DecodingState {
+    fn start_time_map(&self) -> HashMap<JobId, usize> {
+        self.start_times
+            .iter()
+            .enumerate()
+            .filter_map(|(job_id, start_time)| Some((job_id as JobId, (*start_time)?)))
+            .collect()
+    }
+}
+
+/// Partial decoding of the parallel schedule generation scheme
+#[derive(Debug, Clone)]
+struct ParallelState {
+    decoding: DecodingState,
+    /// Current decision point
+    time: usize,
+    /// Jobs not started yet whose predecessors listed earlier all started, in list order
+    eligible: Vec<JobId>,
+    /// Number of predecessors listed earlier that did not start yet, by job number
+    waiting: Vec<usize>,
+    /// Finish times of the started jobs after the previous decision point
+    finish_times: BinaryHeap<Reverse<usize>>,
+}
+
+/// Decoding states kept by an [`IncrementalEvaluation`]
+#[derive(Debug, Clone)]
+enum Snapshots {
+    /// State in front of every position and after the last one
+    Serial(Vec<DecodingState>),
+    Parallel {
+        /// State at the beginning of every decision point
+        states: Vec<ParallelState>,
+        /// First decision point at which a job could start, by job number
+        first_eligible: Vec<usize>,
+    },
+    None,
+}
+
+/// A decoded schedule prepared for evaluating swaps without decoding its unchanged
+/// prefix again, see [`DAG::prepare_incremental_evaluation`]
+#[derive(Debug, Clone)]
+pub struct IncrementalEvaluation {
+    schedule: Vec<JobId>,
+    /// Position of every listed job, by job number
+    positions: Vec<Option<usize>>,
+    snapshots: Snapshots,
+    value: usize,
+}
+
+impl IncrementalEvaluation {
+    /// Objective value of the schedule without any swap
+    pub fn value(&self) -> usize {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use psp_lib_parser::{parse_patterson, parse_psp_lib, parse_rcpsp_max, ValidationError};
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    use super::{Decoder, DAG};
+    use crate::{
+        portfolio::{Objective, ProjectOutcome},
+        test_fixtures::{
+            j30_network, j30_problem, network, patterson_network, rank_order, EMPTY_INSTANCE,
+            J30_INSTANCE, SINGLE_JOB_INSTANCE,
+        },
+        verifier::verify,
+    };
+
+    static RCPSP_MAX_TEST_FILE: &str = include_str!("../../examples/rcpsp_max_example.sch");
+    static MULTI_PROJECT_TEST_FILE: &str = include_str!("../../examples/multi_project.sm");
+    static MULTI_MODE_TEST_FILE: &str = include_str!("../../examples/multi_mode.mm");
+
+    #[test]
+    fn finish_to_start_time_lags() {
+        let dag = j30_network();
+
+        // Job 2 has a duration of 9
+        assert_eq!(dag.time_lag(2, 8), Some(9));
+        assert_eq!(dag.time_lag(8, 2), None);
+    }
+
+    #[test]
+    fn invalid_instances() {
+        // Job 5 demands more units of resource 1 than the 29 available
+        let mut psp = j30_problem();
+        psp.request_durations[4].resources[0] = 30;
+
+        let error = DAG::new(psp, 10).err().unwrap();
+        assert_eq!(
+            error.errors,
+            vec![ValidationError::DemandExceedsCapacity {
+                job: 5,
+                mode: 1,
+                resource: 1,
+                demand: 30,
+                capacity: 29
+            }]
+        );
+        assert!(error
+            .to_string()
+            .starts_with("Invalid instance: Job 5 demands 30 units"));
+
+        // Arcs listed twice, and a single job that cannot be both source and sink
+        let duplicate_arc = parse_patterson("3 1\n10\n0 0 2 2 2\n5 3 1 3\n0 0 0\n").unwrap();
+        assert_eq!(
+            DAG::new(duplicate_arc, 10).err().unwrap().errors,
+            vec![ValidationError::DuplicateSuccessor {
+                job: 1,
+                successor: 2
+            }]
+        );
+        let single_node = parse_patterson("1 1\n10\n0 0 0\n").unwrap();
+        assert!(matches!(
+            DAG::new(single_node, 10).err().unwrap().errors[..],
+            [ValidationError::ProjectJobCountMismatch { .. }]
+        ));
+    }
+
+    #[test]
+    fn degenerate_instances() {
+        for decoder in [Decoder::Serial, Decoder::Parallel, Decoder::BestOf] {
+            // Only the dummy jobs, which take no time
+            let dag = patterson_network(EMPTY_INSTANCE).with_decoder(decoder);
+            let schedule = rank_order(&dag);
+            assert_eq!(dag.compute_execution_time(&schedule, None), 0);
+            assert_eq!(dag.compute_cpm_table(None).horizon, 0);
+            let solution = dag.compute_solution(&schedule);
+            assert_eq!(solution.makespan, 0);
+            assert_eq!(verify(&dag.psp, &solution), vec![]);
+
+            let dag = patterson_network(SINGLE_JOB_INSTANCE).with_decoder(decoder);
+            let schedule = rank_order(&dag);
+            assert_eq!(schedule, vec![2, 3]);
+            assert_eq!(dag.compute_execution_time(&schedule, None), 5);
+            assert_eq!(dag.compute_lower_bound(false), Some((5, vec![1, 2, 3])));
+            let evaluation = dag.prepare_incremental_evaluation(&schedule);
+            assert_eq!(dag.compute_objective_incremental(&evaluation, (2, 3)), 5);
+        }
+    }
+
+    #[test]
+    fn transitive_precedences() {
+        let dag = j30_network();
+        let schedule = rank_order(&dag);
+
+        // Jobs connected through a longer path only, moved next to each other
+        let (before, after) = schedule
+            .iter()
+            .flat_map(|a| schedule.iter().map(move |b| (*a, *b)))
+            .find(|(a, b)| dag.precedes(*a, *b) && dag.time_lag(*a, *b).is_none())
+            .unwrap();
+        assert!(!dag.precedes(after, before));
+        assert!(dag.precedes(1, dag.psp.jobs as u32));
+
+        let mut schedule: Vec<_> = schedule.into_iter().filter(|job| *job != after).collect();
+        let position = schedule.iter().position(|job| *job == before).unwrap();
+        schedule.insert(position + 1, after);
+
+        let swap = (before.min(after), before.max(after));
+        assert!(!dag
+            .compute_reduced_neighborhood_moves(&schedule, 10)
+            .contains(&swap));
+        assert!(!dag
+            .filtered_reduced_neighborhood(&schedule)
+            .contains(&&(position, position + 1)));
+    }
+
+    #[test]
+    fn activity_list_repair() {
+        let dag = j30_network();
+        let schedule = rank_order(&dag);
+        assert!(dag.is_precedence_feasible(&schedule));
+        assert_eq!(dag.repair_activity_list(&schedule), schedule);
+
+        let reversed: Vec<_> = schedule.iter().rev().copied().collect();
+        let violation = dag.find_precedence_violation(&reversed).unwrap();
+        assert!(dag.precedes(violation.predecessor, violation.successor));
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for mut permutation in [reversed, schedule.clone(), schedule] {
+            permutation.shuffle(&mut rng);
+            let repaired = dag.repair_activity_list(&permutation);
+
+            assert!(dag.is_precedence_feasible(&repaired));
+            let mut jobs = repaired.clone();
+            jobs.sort_unstable();
+            permutation.sort_unstable();
+            assert_eq!(jobs, permutation);
+        }
+
+        // Only the violating job is moved
+        let mut swapped = dag.repair_activity_list(&[3, 2, 4, 5]);
+        assert_eq!(swapped, vec![3, 2, 4, 5]);
+        swapped = dag.repair_activity_list(&[32, 3, 2]);
+        assert_eq!(swapped, vec![3, 2, 32]);
+    }
+
+    #[test]
+    fn critical_path_passes() {
+        // Both instances state their critical path length as MPM-Time
+        for (file, mpm_time) in [
+            (J30_INSTANCE, 56),
+            (include_str!("../../examples/j1201_1.sm"), 99),
+        ] {
+            let dag = network(parse_psp_lib(file).unwrap());
+            let sink = dag.psp.jobs as u32;
+
+            let (length, path) = dag.compute_lower_bound(false).unwrap();
+            assert_eq!(length, mpm_time);
+            assert_eq!((path[0], path[path.len() - 1]), (1, sink));
+            let path_duration: u32 = path.iter().map(|job| dag.duration(*job).unwrap()).sum();
+            assert_eq!(path_duration as usize, mpm_time);
+            assert!(path
+                .windows(2)
+                .all(|arc| dag.time_lag(arc[0], arc[1]).is_some()));
+
+            let (length, path) = dag.compute_lower_bound(true).unwrap();
+            assert_eq!(length, mpm_time);
+            assert_eq!((path[0], path[path.len() - 1]), (sink, 1));
+
+            let longest_path = dag.find_longest_path().unwrap();
+            assert_eq!(
+                (longest_path[0], longest_path[longest_path.len() - 1]),
+                (1, sink)
+            );
+        }
+    }
+
+    #[test]
+    fn cpm_table() {
+        let dag = j30_network();
+
+        let table = dag.compute_cpm_table(None);
+        assert_eq!(table.horizon, 56);
+        assert_eq!(table.jobs.len(), 32);
+        // Job 2 (duration 9) starts the project, its successor 8 cannot start earlier
+        let job = table.get(2).unwrap();
+        assert_eq!((job.earliest_start, job.earliest_finish), (0, 9));
+        assert!(table.get(8).unwrap().earliest_start >= 9);
+
+        let critical_jobs = table.critical_jobs();
+        let (_, critical_path) = dag.compute_lower_bound(false).unwrap();
+        assert!(critical_path.iter().all(|job| critical_jobs.contains(job)));
+        for timing in &table.jobs {
+            assert_eq!(
+                timing.latest_start - timing.earliest_start,
+                timing.total_float
+            );
+            assert!(timing.free_float <= timing.total_float);
+            assert!(timing.latest_finish <= table.horizon);
+        }
+
+        // A longer horizon adds the same float to every job
+        let table_with_horizon = dag.compute_cpm_table(Some(dag.psp.horizon));
+        assert_eq!(table_with_horizon.horizon, dag.psp.horizon);
+        for (timing, timing_with_horizon) in table.jobs.iter().zip(&table_with_horizon.jobs) {
+            assert_eq!(timing.earliest_start, timing_with_horizon.earliest_start);
+            assert_eq!(
+                timing.total_float + dag.psp.horizon - 56,
+                timing_with_horizon.total_float
+            );
+        }
+        assert_eq!(
+            table_with_horizon.get(32).unwrap().free_float,
+            dag.psp.horizon - 56
+        );
+        assert_eq!(table_with_horizon.critical_jobs(), vec![]);
+
+        // Only the network decides the order, in which arcs listed twice are merged
+        let mut dag = dag;
+        let relation = &mut dag.psp.precedence_relations[1];
+        relation.successors.push(relation.successors[0]);
+        assert_eq!(dag.compute_cpm_table(None), table);
+    }
+
+    #[test]
+    fn decoded_solution() {
+        let dag = j30_network();
+        let schedule = rank_order(&dag);
+
+        let solution = dag.compute_solution(&schedule);
+        assert_eq!(solution.jobs.len(), 32);
+        assert_eq!(
+            solution.makespan,
+            dag.compute_execution_time(&schedule, None)
+        );
+        assert_eq!(verify(&dag.psp, &solution), vec![]);
+        assert!(solution
+            .resource_usage
+            .iter()
+            .all(|usage| usage.len() == solution.makespan));
+    }
+
+    #[test]
+    fn parallel_decoder() {
+        let psp = j30_problem();
+        let schedule = rank_order(&network(psp.clone()));
+
+        let makespans: Vec<usize> = [Decoder::Serial, Decoder::Parallel, Decoder::BestOf]
             .into_iter()
-            .zip(resources.remove(0))
-            .zip(resources.remove(0))
-            .zip(resources.remove(0))
-            .map(|entry| (entry.0 .0 .0, entry.0 .0 .1, entry.0 .1, entry.1))
-            .filter(|&element| element != (0, 0, 0, 0))
-            .count()
+            .map(|decoder| {
+                let dag = network(psp.clone()).with_decoder(decoder);
+                let solution = dag.compute_solution(&schedule);
+                assert_eq!(verify(&dag.psp, &solution), vec![]);
+
+                assert_eq!(
+                    solution.makespan,
+                    dag.compute_execution_time(&schedule, None)
+                );
+                solution.makespan
+            })
+            .collect();
+
+        assert_eq!(makespans[2], makespans[0].min(makespans[1]));
+    }
+
+    #[test]
+    fn demand_beyond_capacity() {
+        // Instances are validated when building the network, but the capacity can be
+        // lowered afterwards
+        let psp = parse_patterson("3 1\n11\n0 0 1 2\n4 11 1 3\n0 0 0\n").unwrap();
+        let schedule = [2, 3];
+
+        for decoder in [Decoder::Serial, Decoder::Parallel, Decoder::BestOf] {
+            let mut dag = network(psp.clone()).with_decoder(decoder);
+            dag.psp.resource_availabilities.resources[0] = 10;
+
+            assert_eq!(dag.compute_execution_time(&schedule, None), 4);
+            let evaluation = dag.prepare_incremental_evaluation(&schedule);
+            assert_eq!(evaluation.value(), 4);
+            assert_eq!(dag.compute_objective_incremental(&evaluation, (2, 3)), 4);
+        }
+    }
+
+    #[test]
+    fn incremental_evaluation() {
+        for (psp, objective) in [
+            (j30_problem(), Objective::Makespan),
+            (
+                parse_psp_lib(MULTI_PROJECT_TEST_FILE).unwrap(),
+                Objective::TotalWeightedTardiness,
+            ),
+            (
+                parse_rcpsp_max(RCPSP_MAX_TEST_FILE).unwrap(),
+                Objective::Makespan,
+            ),
+        ] {
+            for decoder in [Decoder::Serial, Decoder::Parallel, Decoder::BestOf] {
+                let dag = network(psp.clone())
+                    .with_objective(objective)
+                    .with_decoder(decoder);
+                let schedule = rank_order(&dag);
+
+                let evaluation = dag.prepare_incremental_evaluation(&schedule);
+                assert_eq!(evaluation.value(), dag.compute_objective(&schedule, None));
+
+                // Every swap is evaluated, including those violating precedence relations
+                for (index, job_a) in schedule.iter().enumerate() {
+                    for job_b in &schedule[index + 1..] {
+                        assert_eq!(
+                            dag.compute_objective_incremental(&evaluation, (*job_a, *job_b)),
+                            dag.compute_objective(&schedule, Some((*job_a, *job_b)))
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn makespan_without_resource_usage() {
+        let mut psp = j30_problem();
+        for request in &mut psp.request_durations {
+            request.resources = vec![0; 4];
+        }
+        let dag = network(psp);
+        let schedule = rank_order(&dag);
+
+        // No time slot is busy, yet the jobs take the critical path length
+        assert_eq!(dag.compute_execution_time(&schedule, None), 56);
+        assert_eq!(dag.compute_solution(&schedule).makespan, 56);
+    }
+
+    #[test]
+    fn generalized_time_lags() {
+        let dag = network(parse_rcpsp_max(RCPSP_MAX_TEST_FILE).unwrap());
+
+        assert_eq!(dag.time_lag(2, 5), Some(5));
+        assert_eq!(dag.time_lag(3, 2), Some(-6));
+        assert_eq!(dag.compute_job_execution_ranks()[0], vec![2, 3]);
+    }
+
+    #[test]
+    fn multi_project_outcomes() {
+        let dag = DAG::new(parse_psp_lib(MULTI_PROJECT_TEST_FILE).unwrap(), 3)
+            .unwrap()
+            .with_objective(Objective::TotalWeightedTardiness);
+        let schedule = rank_order(&dag);
+        assert_eq!(schedule, vec![2, 5, 3, 4, 6, 7, 8]);
+
+        // Project 2 is released at time 2, thus job 5 cannot start right away
+        assert_eq!(
+            dag.compute_project_outcomes(&schedule),
+            vec![
+                ProjectOutcome {
+                    project: 1,
+                    release_date: 0,
+                    due_date: 8,
+                    completion: 10,
+                    makespan: 10,
+                    tardiness: 2,
+                    weighted_tardiness: 4,
+                    delay: 3,
+                },
+                ProjectOutcome {
+                    project: 2,
+                    release_date: 2,
+                    due_date: 9,
+                    completion: 10,
+                    makespan: 8,
+                    tardiness: 1,
+                    weighted_tardiness: 1,
+                    delay: 2,
+                },
+            ]
+        );
+        assert_eq!(dag.compute_objective(&schedule, None), 5);
+        assert_eq!(dag.compute_objective(&schedule, Some((3, 4))), 2);
+
+        let dag = dag.with_objective(Objective::TotalProjectDelay);
+        assert_eq!(dag.compute_objective(&schedule, Some((3, 4))), 2);
+    }
+
+    #[test]
+    fn budget_repair_and_rejection() {
+        let psp = parse_psp_lib(MULTI_MODE_TEST_FILE).unwrap();
+        let schedule: Vec<u32> = (2..=12).collect();
+
+        // The first modes exceed the second nonrenewable budget and are repaired
+        let dag = DAG::new(psp.clone(), 5).unwrap();
+        assert!(dag.budget_violations().is_empty());
+        assert_ne!(dag.mode_assignment(), &crate::modes::first_modes(&psp));
+        assert!(dag.compute_execution_time(&schedule, None) < usize::MAX);
+
+        let dag = dag.with_mode_assignment(crate::modes::first_modes(&psp));
+        assert_eq!(dag.time_lag(2, 5), Some(4));
+        assert_eq!(dag.compute_execution_time(&schedule, None), usize::MAX);
+    }
+
+    #[test]
+    fn doubly_constrained_resources() {
+        let mut psp = j30_problem();
+        psp.resources.renewable = 3;
+        psp.resources.doubly_constrained = 1;
+        let schedule: Vec<u32> = (2..=32).collect();
+
+        // A total of 21 units cannot cover all periods of the single-mode jobs
+        let errors = DAG::new(psp.clone(), 10).err().unwrap().errors;
+        assert!(matches!(
+            errors[..],
+            [ValidationError::BudgetExceeded { resource: 4, .. }]
+        ));
+
+        // With a sufficient budget, the per-period capacity applies like a renewable one
+        psp.resource_availabilities.resources[3] = 1000;
+        let mut renewable = j30_problem();
+        renewable.resource_availabilities.resources[3] = 1000;
+        assert_eq!(
+            network(psp).compute_execution_time(&schedule, None),
+            network(renewable).compute_execution_time(&schedule, None)
+        );
     }
 }
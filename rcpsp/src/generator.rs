@@ -0,0 +1,474 @@
+//! Random single-mode instances in the spirit of ProGen (Kolisch, Sprecher and Drexl, 1995)
+
+use std::fmt;
+
+use hashbrown::HashSet;
+use psp_lib_parser::structs::{
+    JobId, PspLibPrecedenceRelation, PspLibProblem, PspLibProblemResources,
+    PspLibProjectInformation, PspLibRequestDuration, PspLibResourceAvailability,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Parameters controlling the size and difficulty of generated instances
+#[derive(Debug, Clone)]
+pub struct GeneratorOptions {
+    /// Number of non-dummy jobs
+    pub jobs: usize,
+    /// Number of renewable resources
+    pub resources: usize,
+    /// Average number of non-redundant arcs per job, including the arcs of the dummy jobs
+    pub network_complexity: f64,
+    /// Average fraction of the resources requested by a job, between 0 and 1
+    pub resource_factor: f64,
+    /// Scarcity of the resources between 0 (tightest) and 1 (no conflicts at all)
+    pub resource_strength: f64,
+    pub min_duration: u32,
+    pub max_duration: u32,
+    pub min_demand: u32,
+    pub max_demand: u32,
+    pub seed: u64,
+}
+
+impl Default for GeneratorOptions {
+    /// Parameters of the J30 set with medium complexity, factor and strength
+    fn default() -> Self {
+        Self {
+            jobs: 30,
+            resources: 4,
+            network_complexity: 1.5,
+            resource_factor: 0.5,
+            resource_strength: 0.5,
+            min_duration: 1,
+            max_duration: 10,
+            min_demand: 1,
+            max_demand: 10,
+            seed: 0,
+        }
+    }
+}
+
+impl GeneratorOptions {
+    /// Checks that every parameter lies within its range, as the random number generator
+    /// cannot sample from empty or undefined ranges
+    pub fn validate(&self) -> Result<(), InvalidOptions> {
+        for (parameter, value) in [
+            ("resource factor", self.resource_factor),
+            ("resource strength", self.resource_strength),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(InvalidOptions::NotAFraction { parameter, value });
+            }
+        }
+
+        if !self.network_complexity.is_finite() || self.network_complexity < 0.0 {
+            return Err(InvalidOptions::NetworkComplexity(self.network_complexity));
+        }
+
+        for (parameter, min, max) in [
+            ("duration", self.min_duration, self.max_duration),
+            ("demand", self.min_demand, self.max_demand),
+        ] {
+            if min > max {
+                return Err(InvalidOptions::EmptyRange {
+                    parameter,
+                    min,
+                    max,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Options rejected by [`generate`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidOptions {
+    /// A resource factor or strength outside of 0 and 1, or not a number at all
+    NotAFraction { parameter: &'static str, value: f64 },
+    /// A negative, infinite or undefined network complexity
+    NetworkComplexity(f64),
+    /// A minimum duration or demand above its maximum
+    EmptyRange {
+        parameter: &'static str,
+        min: u32,
+        max: u32,
+    },
+}
+
+impl fmt::Display for InvalidOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAFraction { parameter, value } => {
+                write!(f, "{parameter} has to be between 0 and 1, but is {value}")
+            }
+            Self::NetworkComplexity(value) => write!(
+                f,
+                "network complexity has to be a non-negative number, but is {value}"
+            ),
+            Self::EmptyRange {
+                parameter,
+                min,
+                max,
+            } => write!(
+                f,
+                "minimum {parameter} {min} exceeds the maximum {parameter} {max}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidOptions {}
+
+/// Generates a random instance, the same options always yield the same instance.
+///
+/// Jobs are numbered topologically, job 1 is the supersource and job `jobs + 2` the
+/// supersink.
+pub fn generate(options: &GeneratorOptions) -> Result<PspLibProblem, InvalidOptions> {
+    options.validate()?;
+
+    let mut rng = StdRng::seed_from_u64(options.seed);
+
+    let successors = generate_network(options, &mut rng);
+    let total_jobs = options.jobs + 2;
+    let sink = total_jobs as JobId;
+
+    let durations: Vec<u32> = (1..=sink)
+        .map(|job| {
+            if job == 1 || job == sink {
+                0
+            } else {
+                rng.gen_range(options.min_duration..=options.max_duration)
+            }
+        })
+        .collect();
+
+    let demands: Vec<Vec<u32>> = (1..=sink)
+        .map(|job| {
+            if job == 1 || job == sink {
+                return vec![0; options.resources];
+            }
+
+            let mut demand: Vec<u32> = (0..options.resources)
+                .map(|_| {
+                    if rng.gen_bool(options.resource_factor) {
+                        rng.gen_range(options.min_demand..=options.max_demand)
+                    } else {
+                        0
+                    }
+                })
+                .collect();
+
+            // Any request at all keeps the resource factor close to the requested one
+            if options.resource_factor > 0.0
+                && options.resources > 0
+                && demand.iter().all(|amount| *amount == 0)
+            {
+                let resource = rng.gen_range(0..options.resources);
+                demand[resource] = rng.gen_range(options.min_demand..=options.max_demand);
+            }
+
+            demand
+        })
+        .collect();
+
+    // Earliest start times without resource constraints, jobs are topologically ordered
+    let mut earliest_starts = vec![0_usize; total_jobs];
+    for (job, job_successors) in successors.iter().enumerate() {
+        let finish = earliest_starts[job] + durations[job] as usize;
+        for successor in job_successors {
+            let successor = *successor as usize - 1;
+            earliest_starts[successor] = earliest_starts[successor].max(finish);
+        }
+    }
+    let critical_path = earliest_starts[total_jobs - 1];
+
+    let capacities = (0..options.resources)
+        .map(|resource| {
+            // Capacity between the largest single demand and the peak demand of the
+            // earliest start schedule, as defined by the resource strength
+            let min_capacity = demands
+                .iter()
+                .map(|demand| demand[resource])
+                .max()
+                .unwrap_or(0);
+
+            // Summed up without overflowing for large demands, capacities beyond the
+            // range of u32 are capped
+            let mut usage = vec![0_u64; critical_path + 1];
+            for job in 0..total_jobs {
+                let start = earliest_starts[job];
+                for used in &mut usage[start..start + durations[job] as usize] {
+                    *used += demands[job][resource] as u64;
+                }
+            }
+            let min_capacity = min_capacity as u64;
+            let max_capacity = usage.into_iter().max().unwrap_or(0).max(min_capacity);
+
+            let capacity = min_capacity
+                + (options.resource_strength * (max_capacity - min_capacity) as f64).round() as u64;
+            u32::try_from(capacity).unwrap_or(u32::MAX)
+        })
+        .collect();
+
+    Ok(PspLibProblem {
+        file_with_basedata: format!("gen{}_.bas", options.jobs),
+        initial_rng: options.seed as usize,
+        projects: 1,
+        jobs: total_jobs,
+        horizon: durations.iter().map(|duration| *duration as usize).sum(),
+        resources: PspLibProblemResources {
+            renewable: options.resources,
+            nonrenewable: 0,
+            doubly_constrained: 0,
+        },
+        project_info: vec![PspLibProjectInformation {
+            number: 1,
+            jobs: options.jobs as JobId,
+            relative_date: 0,
+            due_date: critical_path as u32,
+            tard_cost: rng.gen_range(1..=30),
+            mpm_time: critical_path as u32,
+        }],
+        precedence_relations: successors
+            .into_iter()
+            .enumerate()
+            .map(|(job, successors)| PspLibPrecedenceRelation {
+                job_number: job as JobId + 1,
+                mode_count: 1,
+                successor_count: successors.len() as u32,
+                successors,
+                time_lags: vec![],
+            })
+            .collect(),
+        request_durations: durations
+            .into_iter()
+            .zip(demands)
+            .enumerate()
+            .map(|(job, (duration, resources))| PspLibRequestDuration {
+                job_number: job as JobId + 1,
+                mode: 1,
+                duration,
+                resources,
+            })
+            .collect(),
+        resource_availabilities: PspLibResourceAvailability {
+            resources: capacities,
+        },
+    })
+}
+
+/// Generates the sorted successor lists of all jobs, indexed by job number - 1
+fn generate_network(options: &GeneratorOptions, rng: &mut StdRng) -> Vec<Vec<JobId>> {
+    let jobs = options.jobs;
+    let sink = jobs as JobId + 2;
+    let mut successors: Vec<HashSet<JobId>> = vec![HashSet::new(); jobs + 2];
+
+    // Every job gets a single predecessor, the first jobs start right after the source
+    let start_jobs = rng.gen_range(1..=jobs.clamp(1, 3));
+    for job in 2..jobs as JobId + 2 {
+        let predecessor = if (job as usize) < start_jobs + 2 {
+            1
+        } else {
+            rng.gen_range(2..job)
+        };
+        successors[predecessor as usize - 1].insert(job);
+    }
+
+    // Add further arcs between unrelated jobs until the network complexity is reached
+    let arc_target = (options.network_complexity * (jobs + 2) as f64).round() as usize;
+    let count_arcs = |successors: &[HashSet<JobId>]| {
+        let final_jobs = successors[1..=jobs]
+            .iter()
+            .filter(|job_successors| job_successors.is_empty())
+            .count();
+
+        successors.iter().map(HashSet::len).sum::<usize>() + final_jobs
+    };
+
+    let mut attempts = 0;
+    while jobs >= 2
+        && count_arcs(&successors) < arc_target
+        && attempts < arc_target.saturating_mul(100)
+    {
+        attempts += 1;
+
+        let from = rng.gen_range(2..sink - 1);
+        let to = rng.gen_range(from + 1..sink);
+        if !is_reachable(&successors, from, to) {
+            successors[from as usize - 1].insert(to);
+        }
+    }
+
+    // Jobs without successors precede the sink
+    for job_successors in &mut successors[1..=jobs] {
+        if job_successors.is_empty() {
+            job_successors.insert(sink);
+        }
+    }
+
+    successors
+        .into_iter()
+        .map(|job_successors| {
+            let mut job_successors: Vec<JobId> = job_successors.into_iter().collect();
+            job_successors.sort_unstable();
+            job_successors
+        })
+        .collect()
+}
+
+/// Whether `to` can be reached from `from`, in which case an arc between both is redundant
+fn is_reachable(successors: &[HashSet<JobId>], from: JobId, to: JobId) -> bool {
+    let mut stack = vec![from];
+    let mut visited = HashSet::new();
+
+    while let Some(job) = stack.pop() {
+        if job == to {
+            return true;
+        }
+
+        // Jobs are numbered topologically, thus later jobs cannot lead back to `to`
+        if job > to || !visited.insert(job) {
+            continue;
+        }
+
+        stack.extend(successors[job as usize - 1].iter().copied());
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use psp_lib_parser::{parse_psp_lib, validate_psp_lib, write_psp_lib};
+
+    use super::{generate, GeneratorOptions, InvalidOptions};
+
+    #[test]
+    fn generated_instances_round_trip() {
+        for seed in 0..10 {
+            let psp = generate(&GeneratorOptions {
+                seed,
+                ..GeneratorOptions::default()
+            })
+            .unwrap();
+
+            assert!(validate_psp_lib(&psp).is_empty());
+            assert_eq!(parse_psp_lib(&write_psp_lib(&psp).unwrap()).unwrap(), psp);
+        }
+    }
+
+    #[test]
+    fn large_instances_round_trip() {
+        // Job numbers and demands wider than the columns of the PSPLIB files
+        let psp = generate(&GeneratorOptions {
+            jobs: 1200,
+            min_demand: 1_000_000_000,
+            max_demand: u32::MAX,
+            resource_strength: 1.0,
+            ..GeneratorOptions::default()
+        })
+        .unwrap();
+
+        assert!(validate_psp_lib(&psp).is_empty());
+        assert!(psp
+            .resource_availabilities
+            .resources
+            .iter()
+            .all(|capacity| *capacity == u32::MAX));
+        assert_eq!(parse_psp_lib(&write_psp_lib(&psp).unwrap()).unwrap(), psp);
+    }
+
+    #[test]
+    fn generation_is_deterministic() {
+        let options = GeneratorOptions {
+            jobs: 60,
+            seed: 42,
+            ..GeneratorOptions::default()
+        };
+
+        assert_eq!(generate(&options).unwrap(), generate(&options).unwrap());
+        assert_ne!(
+            generate(&options),
+            generate(&GeneratorOptions {
+                seed: 43,
+                ..options.clone()
+            })
+        );
+    }
+
+    #[test]
+    fn generation_parameters() {
+        let psp = generate(&GeneratorOptions {
+            jobs: 120,
+            resources: 2,
+            network_complexity: 2.1,
+            resource_factor: 1.0,
+            resource_strength: 0.0,
+            ..GeneratorOptions::default()
+        })
+        .unwrap();
+
+        assert_eq!(psp.jobs, 122);
+        assert_eq!(psp.resource_availabilities.resources.len(), 2);
+
+        let arcs: usize = psp
+            .precedence_relations
+            .iter()
+            .map(|relation| relation.successors.len())
+            .sum();
+        assert!((arcs as f64 / 122.0 - 2.1).abs() < 0.05);
+
+        // Every job requests every resource, and the capacity is the largest demand
+        for (index, capacity) in psp.resource_availabilities.resources.iter().enumerate() {
+            let requests = psp.request_durations[1..121]
+                .iter()
+                .map(|request| request.resources[index]);
+            assert!(requests.clone().all(|demand| demand > 0));
+            assert_eq!(Some(*capacity), requests.max());
+        }
+    }
+
+    #[test]
+    fn invalid_options() {
+        let defaults = GeneratorOptions::default();
+
+        assert_eq!(
+            generate(&GeneratorOptions {
+                resource_factor: f64::NAN,
+                ..defaults.clone()
+            })
+            .unwrap_err()
+            .to_string(),
+            "resource factor has to be between 0 and 1, but is NaN"
+        );
+        assert_eq!(
+            generate(&GeneratorOptions {
+                resource_strength: 1.5,
+                ..defaults.clone()
+            }),
+            Err(InvalidOptions::NotAFraction {
+                parameter: "resource strength",
+                value: 1.5
+            })
+        );
+        assert_eq!(
+            generate(&GeneratorOptions {
+                network_complexity: f64::INFINITY,
+                ..defaults.clone()
+            }),
+            Err(InvalidOptions::NetworkComplexity(f64::INFINITY))
+        );
+        assert_eq!(
+            generate(&GeneratorOptions {
+                min_demand: 11,
+                ..defaults
+            }),
+            Err(InvalidOptions::EmptyRange {
+                parameter: "demand",
+                min: 11,
+                max: 10
+            })
+        );
+    }
+}
@@ -1,5 +1,15 @@
+pub mod bounds;
+pub mod characteristics;
 pub mod dag;
+pub mod generator;
+pub mod modes;
+pub mod portfolio;
+pub mod resource_profile;
 pub mod scheduler;
+pub mod solution;
 pub mod tabu_list;
+#[cfg(test)]
+mod test_fixtures;
+pub mod verifier;
 
 pub use psp_lib_parser::structs::PspLibProblem;
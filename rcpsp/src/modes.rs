@@ -0,0 +1,215 @@
+//! Mode assignments of multi-mode instances and the resource budgets they consume
+
+use hashbrown::HashMap;
+use psp_lib_parser::structs::{JobId, PspLibProblem, PspLibRequestDuration};
+
+/// Selected mode of every job, numbered as in the PSPLIB files
+pub type ModeAssignment = HashMap<JobId, u32>;
+
+/// A nonrenewable or doubly constrained resource consumed beyond its capacity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetViolation {
+    /// 1-based resource column
+    pub resource: usize,
+    pub consumption: u64,
+    pub capacity: u32,
+}
+
+/// Assigns every job its first executable mode, or its first mode if none is executable
+pub fn first_modes(psp: &PspLibProblem) -> ModeAssignment {
+    let capacities = &psp.resource_availabilities.resources;
+
+    psp.job_modes()
+        .into_iter()
+        .map(|job| {
+            let mode = job
+                .modes
+                .iter()
+                .find(|mode| mode.is_executable(capacities))
+                .unwrap_or(&job.modes[0]);
+
+            (job.job_number, mode.mode)
+        })
+        .collect()
+}
+
+/// Returns the request/duration entry of the mode assigned to a job
+pub fn assigned_mode<'a>(
+    psp: &'a PspLibProblem,
+    assignment: &ModeAssignment,
+    job: JobId,
+) -> Option<&'a PspLibRequestDuration> {
+    let mode = assignment.get(&job)?;
+
+    psp.request_durations
+        .iter()
+        .find(|request| request.job_number == job && request.mode == *mode)
+}
+
+/// Total consumption of every budget resource, as pairs of resource column and consumption
+fn budget_consumption(psp: &PspLibProblem, assignment: &ModeAssignment) -> Vec<(usize, u64)> {
+    psp.resources
+        .budget_indices()
+        .into_iter()
+        .filter_map(|index| {
+            let kind = psp.resources.kind(index)?;
+            let consumption = assignment
+                .keys()
+                .filter_map(|job| assigned_mode(psp, assignment, *job))
+                .map(|request| request.consumption(index, kind))
+                .sum();
+
+            Some((index, consumption))
+        })
+        .collect()
+}
+
+fn capacity(psp: &PspLibProblem, index: usize) -> u32 {
+    psp.resource_availabilities
+        .resources
+        .get(index)
+        .copied()
+        .unwrap_or(0)
+}
+
+/// Lists all budget resources the assignment consumes beyond their capacity
+pub fn budget_violations(psp: &PspLibProblem, assignment: &ModeAssignment) -> Vec<BudgetViolation> {
+    budget_consumption(psp, assignment)
+        .into_iter()
+        .filter(|(index, consumption)| *consumption > capacity(psp, *index) as u64)
+        .map(|(index, consumption)| BudgetViolation {
+            resource: index + 1,
+            consumption,
+            capacity: capacity(psp, index),
+        })
+        .collect()
+}
+
+/// Repairs an assignment violating resource budgets by greedily switching modes.
+///
+/// Every step switches the one job whose mode change reduces the total excess
+/// consumption the most, preferring shorter modes on ties. Modes exceeding a capacity
+/// on their own are never switched to. Returns false if no switch reduces the excess
+/// any further while the assignment is still infeasible.
+pub fn repair_mode_assignment(psp: &PspLibProblem, assignment: &mut ModeAssignment) -> bool {
+    let job_modes = psp.job_modes();
+    let capacities = &psp.resource_availabilities.resources;
+
+    loop {
+        let consumption = budget_consumption(psp, assignment);
+        let excess = |consumption: &[(usize, u64)]| -> u64 {
+            consumption
+                .iter()
+                .map(|(index, consumption)| {
+                    consumption.saturating_sub(capacity(psp, *index) as u64)
+                })
+                .sum()
+        };
+
+        let current_excess = excess(&consumption);
+        if current_excess == 0 {
+            return true;
+        }
+
+        let best_switch = job_modes
+            .iter()
+            .filter_map(|job| {
+                let current = assigned_mode(psp, assignment, job.job_number)?;
+
+                job.modes
+                    .iter()
+                    .filter(|mode| mode.mode != current.mode && mode.is_executable(capacities))
+                    .map(|mode| {
+                        let switched: Vec<(usize, u64)> = consumption
+                            .iter()
+                            .map(|&(index, total)| {
+                                let kind = psp.resources.kind(index).unwrap();
+
+                                (
+                                    index,
+                                    total - current.consumption(index, kind)
+                                        + mode.consumption(index, kind),
+                                )
+                            })
+                            .collect();
+
+                        (
+                            (excess(&switched), mode.duration),
+                            job.job_number,
+                            mode.mode,
+                        )
+                    })
+                    .min()
+            })
+            .min();
+
+        match best_switch {
+            Some(((switched_excess, _), job, mode)) if switched_excess < current_excess => {
+                assignment.insert(job, mode);
+            }
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use psp_lib_parser::parse_psp_lib;
+
+    use super::*;
+
+    static MULTI_MODE_TEST_FILE: &str = include_str!("../../examples/multi_mode.mm");
+
+    #[test]
+    fn repair_exceeded_budget() {
+        let psp = parse_psp_lib(MULTI_MODE_TEST_FILE).unwrap();
+
+        // The first modes consume 52 units of the second nonrenewable resource
+        let mut assignment = first_modes(&psp);
+        assert_eq!(
+            budget_violations(&psp, &assignment),
+            vec![BudgetViolation {
+                resource: 4,
+                consumption: 52,
+                capacity: 42
+            }]
+        );
+
+        assert!(repair_mode_assignment(&psp, &mut assignment));
+        assert!(budget_violations(&psp, &assignment).is_empty());
+    }
+
+    #[test]
+    fn skip_non_executable_modes() {
+        // The first mode of job 2 and the mode without any N 2 consumption of every
+        // job demand more units of R 1 than available
+        let mut psp = parse_psp_lib(MULTI_MODE_TEST_FILE).unwrap();
+        psp.request_durations[1].resources[0] = 10;
+        for request in &mut psp.request_durations {
+            if request.resources[3] == 0 && request.duration > 0 {
+                request.resources[0] = 10;
+            }
+        }
+        let capacities = &psp.resource_availabilities.resources;
+
+        let mut assignment = first_modes(&psp);
+        assert_eq!(assignment[&2], 3);
+        repair_mode_assignment(&psp, &mut assignment);
+        for job in assignment.keys() {
+            assert!(assigned_mode(&psp, &assignment, *job)
+                .unwrap()
+                .is_executable(capacities));
+        }
+    }
+
+    #[test]
+    fn reject_unrepairable_budget() {
+        let mut psp = parse_psp_lib(MULTI_MODE_TEST_FILE).unwrap();
+        psp.resource_availabilities.resources[2] = 0;
+        psp.resource_availabilities.resources[3] = 0;
+
+        let mut assignment = first_modes(&psp);
+        assert!(!repair_mode_assignment(&psp, &mut assignment));
+        assert!(!budget_violations(&psp, &assignment).is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+//! Objectives for portfolios of projects sharing resources (RCMPSP)
+
+use psp_lib_parser::structs::PspLibProjectInformation;
+
+/// The value minimized by the schedulers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Objective {
+    /// Number of time slots until all jobs are finished
+    #[default]
+    Makespan,
+    /// Sum of all project tardinesses, each weighted by the project's tardiness cost
+    TotalWeightedTardiness,
+    /// Sum of the delays of all projects beyond their release date plus critical path
+    /// length. Divided by the number of projects, it is the average project delay.
+    TotalProjectDelay,
+}
+
+/// Timing of a single project within a schedule
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectOutcome {
+    pub project: u32,
+    pub release_date: usize,
+    pub due_date: usize,
+    /// Finish time of the project's last job
+    pub completion: usize,
+    /// Time from the release date to the completion
+    pub makespan: usize,
+    /// Time the completion lies beyond the due date
+    pub tardiness: usize,
+    pub weighted_tardiness: usize,
+    /// Time the makespan exceeds the project's critical path length (MPM-Time)
+    pub delay: usize,
+}
+
+impl ProjectOutcome {
+    pub fn new(info: &PspLibProjectInformation, completion: usize) -> Self {
+        let release_date = info.relative_date as usize;
+        let due_date = info.due_date as usize;
+        let makespan = completion.saturating_sub(release_date);
+        let tardiness = completion.saturating_sub(due_date);
+
+        Self {
+            project: info.number,
+            release_date,
+            due_date,
+            completion,
+            makespan,
+            tardiness,
+            weighted_tardiness: tardiness * info.tard_cost as usize,
+            delay: makespan.saturating_sub(info.mpm_time as usize),
+        }
+    }
+}
+
+/// Sum of the weighted tardiness of all projects
+pub fn total_weighted_tardiness(outcomes: &[ProjectOutcome]) -> usize {
+    outcomes
+        .iter()
+        .map(|outcome| outcome.weighted_tardiness)
+        .sum()
+}
+
+/// Mean delay of all projects, zero for an empty portfolio
+pub fn average_project_delay(outcomes: &[ProjectOutcome]) -> f64 {
+    if outcomes.is_empty() {
+        return 0.0;
+    }
+
+    outcomes.iter().map(|outcome| outcome.delay).sum::<usize>() as f64 / outcomes.len() as f64
+}
@@ -0,0 +1,142 @@
+//! Usage of a resource over time, stored as a step function
+
+/// Usage of a single resource over time.
+///
+/// Only the times at which the usage changes are stored, thus the size of a profile
+/// depends on the number of jobs using the resource instead of the length of the horizon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceProfile {
+    /// Start times of the steps together with the usage from then until the next step,
+    /// ordered by time. The first step starts at time 0, the last one lasts forever.
+    steps: Vec<(usize, u32)>,
+}
+
+impl Default for ResourceProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResourceProfile {
+    /// An unused resource
+    pub fn new() -> Self {
+        Self {
+            steps: vec![(0, 0)],
+        }
+    }
+
+    /// Usage in the time slot starting at `time`
+    pub fn usage_at(&self, time: usize) -> u32 {
+        self.steps[self.step_index(time)].1
+    }
+
+    /// Earliest start at or after `from` at which `demand` units fit the capacity for
+    /// `duration` time slots.
+    ///
+    /// Returns `None` if the demand exceeds the capacity on its own.
+    pub fn earliest_start(
+        &self,
+        from: usize,
+        duration: usize,
+        demand: u32,
+        capacity: u32,
+    ) -> Option<usize> {
+        if demand > capacity {
+            return None;
+        }
+        if duration == 0 || demand == 0 {
+            return Some(from);
+        }
+
+        let mut start = from;
+        let mut index = self.step_index(from);
+        while index < self.steps.len() {
+            let (time, usage) = self.steps[index];
+            if time >= start + duration {
+                break;
+            }
+
+            // An overloaded step moves the start to its end, the last step lasts forever
+            // but is never used, as every usage ends eventually
+            if usage + demand > capacity {
+                start = self.steps.get(index + 1)?.0;
+            }
+            index += 1;
+        }
+
+        Some(start)
+    }
+
+    /// Adds `demand` units of usage to the `duration` time slots starting at `start`
+    pub fn add(&mut self, start: usize, duration: usize, demand: u32) {
+        if duration == 0 || demand == 0 {
+            return;
+        }
+
+        let first = self.split_at(start);
+        let end = self.split_at(start + duration);
+        for (_, usage) in &mut self.steps[first..end] {
+            *usage += demand;
+        }
+    }
+
+    /// Usage in every time slot up to the end of the last usage
+    pub fn to_periods(&self) -> Vec<u32> {
+        let end = self.steps.last().map(|(time, _)| *time).unwrap_or(0);
+
+        (0..end).map(|time| self.usage_at(time)).collect()
+    }
+
+    /// Index of the step containing `time`
+    fn step_index(&self, time: usize) -> usize {
+        self.steps.partition_point(|(step, _)| *step <= time) - 1
+    }
+
+    /// Ensures a step starts at `time`, returning its index
+    fn split_at(&mut self, time: usize) -> usize {
+        let index = self.step_index(time);
+        let (step, usage) = self.steps[index];
+        if step == time {
+            return index;
+        }
+
+        self.steps.insert(index + 1, (time, usage));
+        index + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResourceProfile;
+
+    #[test]
+    fn add_and_query_usage() {
+        let mut profile = ResourceProfile::new();
+        profile.add(2, 3, 4);
+        profile.add(4, 2, 1);
+        profile.add(0, 0, 7);
+
+        assert_eq!(profile.to_periods(), vec![0, 0, 4, 4, 5, 1]);
+        assert_eq!(profile.usage_at(3), 4);
+        assert_eq!(profile.usage_at(100), 0);
+    }
+
+    #[test]
+    fn earliest_feasible_start() {
+        let mut profile = ResourceProfile::new();
+        profile.add(2, 3, 4);
+        profile.add(4, 2, 1);
+
+        // Fits in front of the first usage, or in parallel to the lower usage
+        assert_eq!(profile.earliest_start(0, 2, 5, 5), Some(0));
+        assert_eq!(profile.earliest_start(0, 3, 5, 5), Some(6));
+        assert_eq!(profile.earliest_start(0, 2, 1, 5), Some(0));
+        assert_eq!(profile.earliest_start(1, 10, 1, 5), Some(5));
+        assert_eq!(profile.earliest_start(3, 1, 0, 5), Some(3));
+        assert_eq!(profile.earliest_start(0, 1, 6, 5), None);
+
+        // Long horizons do not cost more than short ones
+        profile.add(1_000_000, 500_000, 5);
+        assert_eq!(profile.earliest_start(999_999, 2, 1, 5), Some(1_500_000));
+    }
+}
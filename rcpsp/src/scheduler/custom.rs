@@ -1,8 +1,8 @@
 use log::{debug, info};
-use psp_lib_parser::structs::PspLibProblem;
+use psp_lib_parser::structs::{JobId, PspLibProblem};
 
 use crate::{
-    dag::DAG,
+    dag::{InvalidInstance, DAG},
     tabu_list::{simple_tabu_list::SimpleTabuList, TabuList},
 };
 
@@ -14,7 +14,10 @@ use std::thread;
 
 use super::{OptimizedSchedule, SchedulerOptions};
 
-pub fn scheduler(psp: PspLibProblem, mut options: SchedulerOptions) -> OptimizedSchedule {
+pub fn scheduler(
+    psp: PspLibProblem,
+    mut options: SchedulerOptions,
+) -> Result<OptimizedSchedule, InvalidInstance> {
     //==========settings
     options.number_of_iterations = 4000;
     //options.swap_range=10;
@@ -48,22 +51,23 @@ pub fn scheduler(psp: PspLibProblem, mut options: SchedulerOptions) -> Optimized
     let diversification_iterations = 20;
 
     //==========initialization
-    let dag = DAG::new(psp, options.swap_range);
+    let dag = DAG::new(psp, options.swap_range)?
+        .with_objective(options.objective)
+        .with_decoder(options.decoder);
 
-    let lower_bound = dag.compute_lower_bound(false);
-    info!("lower_bound: {lower_bound:?}");
+    let lower_bound = dag.compute_objective_lower_bound();
+    info!("lower_bound: {lower_bound}");
     info!("options: {options:?}");
-    let lower_bound = lower_bound.map(|lb| lb.0).unwrap_or(0);
 
     // Compute initial solution
-    let mut job_execution_ranks: Vec<Vec<u8>> = vec![vec![1]]; //fixes the missing first job
+    let mut job_execution_ranks: Vec<Vec<JobId>> = vec![vec![1]]; //fixes the missing first job
     job_execution_ranks.append(&mut dag.compute_job_execution_ranks());
     let job_execution_ranks = job_execution_ranks;
 
-    let schedule: Vec<u8> = job_execution_ranks.clone().into_iter().flatten().collect();
-    let mut schedules: Vec<Vec<u8>> = vec![schedule];
+    let schedule: Vec<JobId> = job_execution_ranks.clone().into_iter().flatten().collect();
+    let mut schedules: Vec<Vec<JobId>> = vec![schedule];
 
-    let mut extra_initial_solutions: Vec<Vec<u8>> = (0..schedule_count - 1)
+    let mut extra_initial_solutions: Vec<Vec<JobId>> = (0..schedule_count - 1)
         .map(|_| {
             job_execution_ranks
                 .clone()
@@ -75,18 +79,21 @@ pub fn scheduler(psp: PspLibProblem, mut options: SchedulerOptions) -> Optimized
                 .collect()
         })
         .collect();
-    //let mut extra_initial_solutions: Vec<Vec<u8>> = (0..thread_count-1).into_iter().map(|_| job_execution_ranks.clone().into_iter()                                          .flatten().collect()).collect();
+    //let mut extra_initial_solutions: Vec<Vec<JobId>> = (0..thread_count-1).into_iter().map(|_| job_execution_ranks.clone().into_iter()                                          .flatten().collect()).collect();
     schedules.append(&mut extra_initial_solutions);
+    debug_assert!(schedules
+        .iter()
+        .all(|schedule| dag.is_precedence_feasible(schedule)));
     info!("initial_solution: {schedules:?}");
 
     let mut schedule_times: Vec<usize> = schedules
         .iter()
-        .map(|s| dag.compute_execution_time(s, None))
+        .map(|s| dag.compute_objective(s, None))
         .collect();
     let mut global_best_solution_time: usize = *schedule_times.iter().min().unwrap();
-    let mut global_best_solution_schedule: Vec<u8> = schedules
+    let mut global_best_solution_schedule: Vec<JobId> = schedules
         .iter()
-        .map(|s| (s, dag.compute_execution_time(s, None)))
+        .map(|s| (s, dag.compute_objective(s, None)))
         .min_by_key(|(_, time)| *time)
         .unwrap()
         .0
@@ -189,7 +196,7 @@ pub fn scheduler(psp: PspLibProblem, mut options: SchedulerOptions) -> Optimized
                         diversification_iterations,
                         &dag_arc,
                     );
-                    schedule_times[to] = dag_arc.compute_execution_time(&schedules[to], None);
+                    schedule_times[to] = dag_arc.compute_objective(&schedules[to], None);
                     tabu_lists[to] = tabu_lists[from].clone();
                 }
 
@@ -243,14 +250,16 @@ pub fn scheduler(psp: PspLibProblem, mut options: SchedulerOptions) -> Optimized
     info!("best_execution_time: {global_best_solution_time}");
     //info!("best_execution_time2: {}",dag.compute_execution_time(&best_execution_schedule, Some(&(1,2))));
 
-    OptimizedSchedule {
-        schedule: global_best_solution_schedule,
-        duration: global_best_solution_time,
-    }
+    Ok(OptimizedSchedule::new(
+        &dag_arc,
+        global_best_solution_schedule,
+        global_best_solution_time,
+        lower_bound,
+    ))
 }
 
 struct ThreadInfo {
-    schedule: Vec<u8>,
+    schedule: Vec<JobId>,
     schedule_time: usize,
     schedule_id: usize,
     tabu_list: SimpleTabuList,
@@ -260,7 +269,7 @@ struct ThreadInfo {
 }
 
 struct ThreadData {
-    new_schedule: Vec<u8>,
+    new_schedule: Vec<JobId>,
     schedule_id: usize,
     new_schedule_time: usize,
     new_tabu_list: SimpleTabuList,
@@ -315,10 +324,10 @@ fn thread_body(
 }
 
 struct ImproveScheduleArguments<'a> {
-    schedule: Vec<u8>,
+    schedule: Vec<JobId>,
     schedule_time: usize,
     schedule_id: usize,
-    global_best_solution_time: usize, //u8 probably to small for the durations
+    global_best_solution_time: usize,
     max_iterations: u32,
     max_iterations_since_best: u32,
 
@@ -327,8 +336,8 @@ struct ImproveScheduleArguments<'a> {
     tabu_list: SimpleTabuList,
 }
 
-fn improve_schedule(args: ImproveScheduleArguments) -> (Vec<u8>, usize, usize, SimpleTabuList) {
-    //schedule:Vec<u8>,schedule_number:usize,schedule_time
+fn improve_schedule(args: ImproveScheduleArguments) -> (Vec<JobId>, usize, usize, SimpleTabuList) {
+    //schedule:Vec<JobId>,schedule_number:usize,schedule_time
 
     let ImproveScheduleArguments {
         mut schedule,
@@ -353,6 +362,7 @@ fn improve_schedule(args: ImproveScheduleArguments) -> (Vec<u8>, usize, usize, S
     for _iteration in 0..max_iterations {
         let reduced_neighborhood: Vec<&(usize, usize)> =
             dag.filtered_reduced_neighborhood(&schedule);
+        let evaluation = dag.prepare_incremental_evaluation(&schedule);
         match reduced_neighborhood
             //carry swap as identifier for the schedule_time
             .into_iter()
@@ -360,9 +370,9 @@ fn improve_schedule(args: ImproveScheduleArguments) -> (Vec<u8>, usize, usize, S
             .map(|swap| {
                 (
                     swap,
-                    dag.compute_execution_time(
-                        &schedule,
-                        Some((schedule[swap.0], schedule[swap.1])),
+                    dag.compute_objective_incremental(
+                        &evaluation,
+                        (schedule[swap.0], schedule[swap.1]),
                     ),
                 )
             })
@@ -390,6 +400,7 @@ fn improve_schedule(args: ImproveScheduleArguments) -> (Vec<u8>, usize, usize, S
 
         //update schedule
         schedule.swap(best_swap.0, best_swap.1);
+        debug_assert_eq!(dag.find_precedence_violation(&schedule), None);
         debug!("schedule: {schedule:?}");
         let temp = (schedule[best_swap.0], schedule[best_swap.1]);
         debug!("swapped: {temp:?} ; index: {best_swap:?}");
@@ -434,13 +445,14 @@ fn improve_schedule(args: ImproveScheduleArguments) -> (Vec<u8>, usize, usize, S
     )
 }
 
-fn diversify_schedule(mut schedule: Vec<u8>, iterations: u32, dag: &DAG) -> Vec<u8> {
+fn diversify_schedule(mut schedule: Vec<JobId>, iterations: u32, dag: &DAG) -> Vec<JobId> {
     for _ in 0..iterations {
         let random_swap = **dag
             .filtered_reduced_neighborhood(&schedule)
             .choose(&mut thread_rng())
             .unwrap_or(&&(0, 0));
         schedule.swap(random_swap.0, random_swap.1);
+        debug_assert_eq!(dag.find_precedence_violation(&schedule), None);
     }
     schedule
 }
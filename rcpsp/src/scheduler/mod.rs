@@ -1,8 +1,17 @@
+use psp_lib_parser::structs::JobId;
+
+use crate::{
+    dag::{Decoder, DAG},
+    portfolio::Objective,
+    solution::Solution,
+};
+
 pub mod custom;
 pub mod rayon;
 pub mod rayon_multi;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SchedulerOptions {
     pub number_of_iterations: u32,
     pub max_iter_since_best: u32,
@@ -12,9 +21,47 @@ pub struct SchedulerOptions {
     pub iter_since_best_reset: Option<u32>,
     pub schedule_count: u32,
     pub schedule_duration: Option<u64>,
+    pub objective: Objective,
+    pub decoder: Decoder,
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OptimizedSchedule {
-    pub schedule: Vec<u8>,
+    pub schedule: Vec<JobId>,
+    /// Value of the optimized objective, the makespan unless configured otherwise
     pub duration: usize,
+    /// Lower bound of the objective the search could have stopped at
+    pub lower_bound: usize,
+    /// Relative distance of the objective value to the lower bound, 0 if it is optimal.
+    ///
+    /// Not available if the lower bound is 0 but the objective value is not.
+    pub gap: Option<f64>,
+    /// Start and finish times of the best activity list
+    pub solution: Solution,
+}
+
+impl OptimizedSchedule {
+    /// Result of a search, `schedule` being the best activity list found.
+    ///
+    /// Panics if the schedule is empty, as every search starts from a complete one.
+    pub fn new(dag: &DAG, schedule: Vec<JobId>, duration: usize, lower_bound: usize) -> Self {
+        assert!(!schedule.is_empty(), "optimized schedule without any job");
+
+        let gap = if duration <= lower_bound {
+            Some(0.0)
+        } else if lower_bound == 0 {
+            None
+        } else {
+            Some((duration - lower_bound) as f64 / lower_bound as f64)
+        };
+
+        Self {
+            solution: dag.compute_solution(&schedule),
+            schedule,
+            duration,
+            lower_bound,
+            gap,
+        }
+    }
 }
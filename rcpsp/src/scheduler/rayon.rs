@@ -1,33 +1,38 @@
 use std::time::Instant;
 
 use log::{debug, info, trace};
-use psp_lib_parser::structs::PspLibProblem;
+use psp_lib_parser::structs::{JobId, PspLibProblem};
 use rayon::prelude::*;
 
 use crate::{
-    dag::DAG,
+    dag::{InvalidInstance, DAG},
     tabu_list::{simple_tabu_list::SimpleTabuList, TabuList},
 };
 
 use super::{OptimizedSchedule, SchedulerOptions};
 
-pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSchedule {
-    let dag = DAG::new(psp.clone(), options.swap_range);
+pub fn scheduler(
+    psp: PspLibProblem,
+    options: SchedulerOptions,
+) -> Result<OptimizedSchedule, InvalidInstance> {
+    let dag = DAG::new(psp.clone(), options.swap_range)?
+        .with_objective(options.objective)
+        .with_decoder(options.decoder);
 
-    let lower_bound = dag.compute_lower_bound(false);
-    info!("lower bound: {lower_bound:?}");
-    let lower_bound = lower_bound.map(|lb| lb.0).unwrap_or(0);
+    let lower_bound = dag.compute_objective_lower_bound();
+    info!("lower bound: {lower_bound}");
 
     // Compute initial solution
-    let mut schedule: Vec<u8> = dag
+    let mut schedule: Vec<JobId> = dag
         .compute_job_execution_ranks()
         .into_iter()
         .flatten()
         .collect();
 
     info!("initial schedule: {schedule:?}");
+    debug_assert_eq!(dag.find_precedence_violation(&schedule), None);
 
-    let execution_time = dag.compute_execution_time(&schedule, None);
+    let execution_time = dag.compute_objective(&schedule, None);
     info!("execution_time: {execution_time}");
 
     let start_time = Instant::now();
@@ -64,18 +69,20 @@ pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSche
         let moves = dag.compute_reduced_neighborhood_moves(&schedule, options.swap_range);
         trace!("moves: {moves:?}");
 
-        // Perform swaps and after each swap reevaluate execution time
+        // Perform swaps and after each swap reevaluate execution time, decoding only the
+        // part of the schedule behind the first swapped job
+        let evaluation = dag.prepare_incremental_evaluation(&schedule);
         let map_op = |(job_a, job_b)| {
-            let execution_time = dag.compute_execution_time(&schedule, Some((job_a, job_b)));
+            let execution_time = dag.compute_objective_incremental(&evaluation, (job_a, job_b));
 
             (execution_time, (job_a, job_b))
         };
-        let filter_op = |(execution_time, (i, j)): &(usize, (u8, u8))| {
+        let filter_op = |(execution_time, (i, j)): &(usize, (JobId, JobId))| {
             tabu_list.is_possible_move(*i as usize, *j as usize)
                 || *execution_time < best_execution_time
         };
 
-        let mut rated_moves: Vec<(usize, (u8, u8))> = {
+        let mut rated_moves: Vec<(usize, (JobId, JobId))> = {
             if options.parallel {
                 moves
                     .into_par_iter()
@@ -99,6 +106,7 @@ pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSche
             let index_b = schedule.iter().position(|&job| job == j).unwrap();
 
             schedule.swap(index_a, index_b);
+            debug_assert_eq!(dag.find_precedence_violation(&schedule), None);
 
             tabu_list.add_turn_to_tabu_list(i as usize, j as usize);
 
@@ -127,8 +135,10 @@ pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSche
     info!("best_execution_schedule: {best_execution_schedule:?}");
     info!("best_execution_time: {best_execution_time}");
 
-    OptimizedSchedule {
-        schedule: best_execution_schedule,
-        duration: best_execution_time,
-    }
+    Ok(OptimizedSchedule::new(
+        &dag,
+        best_execution_schedule,
+        best_execution_time,
+        lower_bound,
+    ))
 }
@@ -1,12 +1,12 @@
 use std::time::Instant;
 
 use log::{debug, info};
-use psp_lib_parser::structs::PspLibProblem;
+use psp_lib_parser::structs::{JobId, PspLibProblem};
 use rand::{prelude::SliceRandom, thread_rng};
 use rayon::prelude::*;
 
 use crate::{
-    dag::DAG,
+    dag::{IncrementalEvaluation, InvalidInstance, DAG},
     tabu_list::{simple_tabu_list::SimpleTabuList, TabuList},
 };
 
@@ -14,41 +14,56 @@ use super::{OptimizedSchedule, SchedulerOptions};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct InterimSchedule {
-    pub schedule: Vec<u8>,
+    pub schedule: Vec<JobId>,
 
     pub best_duration: usize,
-    pub best_schedule: Vec<u8>,
+    pub best_schedule: Vec<JobId>,
 
     pub tabu_list: SimpleTabuList,
 }
 
-type RatedMove = Vec<(usize, (u8, u8))>;
+impl InterimSchedule {
+    /// Starts the search from a schedule, which is the best one found so far
+    fn new(dag: &DAG, schedule: Vec<JobId>, tabu_list: SimpleTabuList) -> Self {
+        Self {
+            best_duration: dag.compute_objective(&schedule, None),
+            best_schedule: schedule.clone(),
+            schedule,
+            tabu_list,
+        }
+    }
+}
 
-pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSchedule {
-    let dag = DAG::new(psp.clone(), options.swap_range);
+type RatedMove = Vec<(usize, (JobId, JobId))>;
 
-    let lower_bound = dag.compute_lower_bound(false);
-    info!("lower bound: {lower_bound:?}");
-    let lower_bound = lower_bound.map(|lb| lb.0).unwrap_or(0);
+pub fn scheduler(
+    psp: PspLibProblem,
+    options: SchedulerOptions,
+) -> Result<OptimizedSchedule, InvalidInstance> {
+    let dag = DAG::new(psp.clone(), options.swap_range)?
+        .with_objective(options.objective)
+        .with_decoder(options.decoder);
+
+    let lower_bound = dag.compute_objective_lower_bound();
+    info!("lower bound: {lower_bound}");
 
     // Compute initial solutions
     let mut schedules: Vec<InterimSchedule> = Vec::with_capacity(options.schedule_count as usize);
 
-    let schedule: Vec<u8> = dag
+    let schedule: Vec<JobId> = dag
         .compute_job_execution_ranks()
         .into_iter()
         .flatten()
         .collect();
-    schedules.push(InterimSchedule {
+    schedules.push(InterimSchedule::new(
+        &dag,
         schedule,
-        best_duration: usize::MAX,
-        best_schedule: vec![],
-        tabu_list: SimpleTabuList::new(psp.jobs, options.tabu_list_size as usize),
-    });
+        SimpleTabuList::new(psp.jobs, options.tabu_list_size as usize),
+    ));
 
     let job_execution_ranks = dag.compute_job_execution_ranks();
     for _ in 1..options.schedule_count {
-        let schedule: Vec<u8> = job_execution_ranks
+        let schedule: Vec<JobId> = job_execution_ranks
             .clone()
             .into_iter()
             .flat_map(|mut x| {
@@ -56,15 +71,20 @@ pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSche
                 x
             })
             .collect();
-        schedules.push(InterimSchedule {
+        debug_assert_eq!(dag.find_precedence_violation(&schedule), None);
+        schedules.push(InterimSchedule::new(
+            &dag,
             schedule,
-            best_duration: usize::MAX,
-            best_schedule: vec![],
-            tabu_list: SimpleTabuList::new(psp.jobs, options.tabu_list_size as usize),
-        });
+            SimpleTabuList::new(psp.jobs, options.tabu_list_size as usize),
+        ));
     }
 
-    let mut best_global_duration = usize::MAX;
+    // Without any improving move, the best initial schedule is the result
+    let mut best_global_duration = schedules
+        .iter()
+        .map(|schedule| schedule.best_duration)
+        .min()
+        .unwrap_or(usize::MAX);
     let mut iter_since_best = 0;
 
     let start_time = Instant::now();
@@ -82,17 +102,19 @@ pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSche
             break;
         }
 
-        // Perform swaps and after each swap reevaluate execution time
-        let map_op = |schedule: Vec<u8>, (job_a, job_b)| {
-            let execution_time = dag.compute_execution_time(&schedule, Some((job_a, job_b)));
+        // Perform swaps and after each swap reevaluate execution time, decoding only the
+        // part of the schedule behind the first swapped job
+        let map_op = |evaluation: &IncrementalEvaluation, (job_a, job_b)| {
+            let execution_time = dag.compute_objective_incremental(evaluation, (job_a, job_b));
 
             (execution_time, (job_a, job_b))
         };
-        let filter_op = |local_tabu_list: SimpleTabuList,
-                         (execution_time, (i, j)): &(usize, (u8, u8))| {
-            local_tabu_list.is_possible_move(*i as usize, *j as usize)
-                || *execution_time < best_global_duration
-        };
+        let filter_op =
+            |local_tabu_list: SimpleTabuList,
+             (execution_time, (i, j)): &(usize, (JobId, JobId))| {
+                local_tabu_list.is_possible_move(*i as usize, *j as usize)
+                    || *execution_time < best_global_duration
+            };
 
         let rated_moves_and_schedule: Vec<(RatedMove, &mut InterimSchedule)> = if options.parallel {
             schedules
@@ -107,11 +129,10 @@ pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSche
                     )
                 })
                 .map(|(feasible_moves, interim_schedule)| {
-                    let mut processed_moves: Vec<(usize, (u8, u8))> = feasible_moves
+                    let evaluation = dag.prepare_incremental_evaluation(&interim_schedule.schedule);
+                    let mut processed_moves: Vec<(usize, (JobId, JobId))> = feasible_moves
                         .into_iter()
-                        .map(|possible_move| {
-                            map_op(interim_schedule.schedule.clone(), possible_move)
-                        })
+                        .map(|possible_move| map_op(&evaluation, possible_move))
                         .filter(|value| filter_op(interim_schedule.tabu_list.clone(), value))
                         .collect();
 
@@ -133,11 +154,10 @@ pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSche
                     )
                 })
                 .map(|(feasible_moves, interim_schedule)| {
-                    let mut processed_moves: Vec<(usize, (u8, u8))> = feasible_moves
+                    let evaluation = dag.prepare_incremental_evaluation(&interim_schedule.schedule);
+                    let mut processed_moves: Vec<(usize, (JobId, JobId))> = feasible_moves
                         .into_iter()
-                        .map(|possible_move| {
-                            map_op(interim_schedule.schedule.clone(), possible_move)
-                        })
+                        .map(|possible_move| map_op(&evaluation, possible_move))
                         .filter(|value| filter_op(interim_schedule.tabu_list.clone(), value))
                         .collect();
 
@@ -164,6 +184,10 @@ pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSche
                     .unwrap();
 
                 interim_schedule.schedule.swap(a, b);
+                debug_assert_eq!(
+                    dag.find_precedence_violation(&interim_schedule.schedule),
+                    None
+                );
                 interim_schedule
                     .tabu_list
                     .add_turn_to_tabu_list(i as usize, j as usize);
@@ -203,8 +227,10 @@ pub fn scheduler(psp: PspLibProblem, options: SchedulerOptions) -> OptimizedSche
     info!("best_execution_schedule: {best_execution_schedule:?}");
     info!("best_global_duration: {best_global_duration}");
 
-    OptimizedSchedule {
-        schedule: best_execution_schedule,
-        duration: best_global_duration,
-    }
+    Ok(OptimizedSchedule::new(
+        &dag,
+        best_execution_schedule,
+        best_global_duration,
+        lower_bound,
+    ))
 }
@@ -0,0 +1,62 @@
+//! Schedules decoded from activity lists, with explicit start and finish times
+
+use psp_lib_parser::structs::JobId;
+
+/// Execution interval of a single job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduledJob {
+    pub job: JobId,
+    /// Mode the job is executed in, solutions without modes refer to single-mode instances
+    #[cfg_attr(feature = "serde", serde(default = "single_mode"))]
+    pub mode: u32,
+    pub start: usize,
+    /// First time slot after the job, equal to its start for jobs without duration
+    pub finish: usize,
+}
+
+#[cfg(feature = "serde")]
+fn single_mode() -> u32 {
+    1
+}
+
+/// Start and finish time of every job together with the resulting resource usage
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Solution {
+    /// Ordered by job number
+    pub jobs: Vec<ScheduledJob>,
+    /// Finish time of the last job
+    pub makespan: usize,
+    /// Usage of the renewable and doubly constrained resources, in the order of the
+    /// instance, in every time slot up to the makespan
+    pub resource_usage: Vec<Vec<u32>>,
+}
+
+impl Solution {
+    pub fn new(mut jobs: Vec<ScheduledJob>, mut resource_usage: Vec<Vec<u32>>) -> Self {
+        jobs.sort_unstable_by_key(|scheduled| scheduled.job);
+        let makespan = jobs
+            .iter()
+            .map(|scheduled| scheduled.finish)
+            .max()
+            .unwrap_or(0);
+
+        for usage in &mut resource_usage {
+            usage.resize(makespan, 0);
+        }
+
+        Self {
+            jobs,
+            makespan,
+            resource_usage,
+        }
+    }
+
+    pub fn get(&self, job: JobId) -> Option<&ScheduledJob> {
+        self.jobs
+            .binary_search_by_key(&job, |scheduled| scheduled.job)
+            .ok()
+            .map(|index| &self.jobs[index])
+    }
+}
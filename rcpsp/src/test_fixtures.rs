@@ -0,0 +1,40 @@
+//! Instances and activity lists shared by the unit tests
+
+use psp_lib_parser::{parse_patterson, parse_psp_lib, structs::JobId};
+
+use crate::{dag::DAG, PspLibProblem};
+
+/// j3011_2 of the J30 set, its optimal makespan of 56 equals its critical path length
+pub static J30_INSTANCE: &str = include_str!("../../examples/j3011_2.sm");
+
+/// Project without any job besides the dummy source and sink
+pub static EMPTY_INSTANCE: &str = "2 1\n10\n0 0 1 2\n0 0 0\n";
+
+/// Project of a single job lasting 5 periods and using 3 of the 10 available units
+pub static SINGLE_JOB_INSTANCE: &str = "3 1\n10\n0 0 1 2\n5 3 1 3\n0 0 0\n";
+
+pub fn j30_problem() -> PspLibProblem {
+    parse_psp_lib(J30_INSTANCE).unwrap()
+}
+
+/// Network of a valid instance, with the swap range used throughout the tests
+pub fn network(psp: PspLibProblem) -> DAG {
+    DAG::new(psp, 10).unwrap()
+}
+
+pub fn j30_network() -> DAG {
+    network(j30_problem())
+}
+
+/// Network of an instance in the Patterson format
+pub fn patterson_network(text: &str) -> DAG {
+    network(parse_patterson(text).unwrap())
+}
+
+/// Activity list scheduling the jobs rank by rank, thus keeping every precedence relation
+pub fn rank_order(dag: &DAG) -> Vec<JobId> {
+    dag.compute_job_execution_ranks()
+        .into_iter()
+        .flatten()
+        .collect()
+}
@@ -0,0 +1,370 @@
+//! Independent check of solutions against the constraints of an instance
+//!
+//! Only the instance itself is consulted, none of the data structures used by the search,
+//! so that published schedules can be certified without trusting the scheduler.
+
+use std::fmt;
+
+use hashbrown::{HashMap, HashSet};
+use psp_lib_parser::structs::{JobId, PspLibProblem, PspLibRequestDuration};
+
+use crate::solution::Solution;
+
+/// A constraint of the instance not kept by a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Violation {
+    /// A job of the instance without a start time
+    MissingJob { job: JobId },
+    /// A job scheduled more than once
+    DuplicateJob { job: JobId },
+    /// A job that is not part of the instance
+    UnknownJob { job: JobId },
+    /// A mode the instance does not define for the job
+    UnknownMode { job: JobId, mode: u32 },
+    /// A finish time not matching the duration of the job's mode
+    WrongFinish {
+        job: JobId,
+        expected: usize,
+        found: usize,
+    },
+    /// A successor starting earlier than the time lag after its predecessor's start allows
+    Precedence {
+        predecessor: JobId,
+        successor: JobId,
+        time_lag: i64,
+        predecessor_start: usize,
+        successor_start: usize,
+    },
+    /// A job starting before the release date of its project
+    ReleaseDate {
+        job: JobId,
+        release_date: usize,
+        start: usize,
+    },
+    /// A renewable or doubly constrained resource used beyond its capacity in a period
+    ResourceOverload {
+        /// 1-based resource column
+        resource: usize,
+        period: usize,
+        usage: u64,
+        capacity: u32,
+    },
+    /// A nonrenewable or doubly constrained resource consumed beyond its capacity
+    BudgetExceeded {
+        /// 1-based resource column
+        resource: usize,
+        consumption: u64,
+        capacity: u32,
+    },
+    /// A claimed makespan differing from the latest finish time
+    Makespan { claimed: usize, actual: usize },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingJob { job } => write!(f, "Job {job} is not scheduled"),
+            Self::DuplicateJob { job } => write!(f, "Job {job} is scheduled more than once"),
+            Self::UnknownJob { job } => write!(f, "Job {job} does not exist"),
+            Self::UnknownMode { job, mode } => write!(f, "Job {job} has no mode {mode}"),
+            Self::WrongFinish {
+                job,
+                expected,
+                found,
+            } => write!(f, "Job {job} finishes at {found} instead of {expected}"),
+            Self::Precedence {
+                predecessor,
+                successor,
+                time_lag,
+                predecessor_start,
+                successor_start,
+            } => write!(
+                f,
+                "Job {successor} starts at {successor_start}, but has to wait {time_lag} after \
+                 job {predecessor} started at {predecessor_start}"
+            ),
+            Self::ReleaseDate {
+                job,
+                release_date,
+                start,
+            } => write!(
+                f,
+                "Job {job} starts at {start}, before its project is released at {release_date}"
+            ),
+            Self::ResourceOverload {
+                resource,
+                period,
+                usage,
+                capacity,
+            } => write!(
+                f,
+                "Resource {resource} is used by {usage} units in period {period}, but only \
+                 {capacity} are available"
+            ),
+            Self::BudgetExceeded {
+                resource,
+                consumption,
+                capacity,
+            } => write!(
+                f,
+                "Resource {resource} is consumed by {consumption} units, but only {capacity} are \
+                 available"
+            ),
+            Self::Makespan { claimed, actual } => {
+                write!(f, "Makespan is claimed as {claimed}, but is {actual}")
+            }
+        }
+    }
+}
+
+/// Checks the start times, modes and makespan of a solution against every precedence
+/// relation, release date and resource capacity of the instance.
+///
+/// Returns all violations found, the solution is valid if there are none.
+pub fn verify(psp: &PspLibProblem, solution: &Solution) -> Vec<Violation> {
+    let mut violations = vec![];
+
+    // Execution of every job, as its start time and its mode's request/duration entry
+    let mut executions: HashMap<JobId, (usize, &PspLibRequestDuration)> = HashMap::new();
+    for scheduled in &solution.jobs {
+        let mut modes = psp
+            .request_durations
+            .iter()
+            .filter(|request| request.job_number == scheduled.job)
+            .peekable();
+        if modes.peek().is_none() {
+            violations.push(Violation::UnknownJob { job: scheduled.job });
+            continue;
+        }
+        let Some(request) = modes.find(|request| request.mode == scheduled.mode) else {
+            violations.push(Violation::UnknownMode {
+                job: scheduled.job,
+                mode: scheduled.mode,
+            });
+            continue;
+        };
+
+        if executions
+            .insert(scheduled.job, (scheduled.start, request))
+            .is_some()
+        {
+            violations.push(Violation::DuplicateJob { job: scheduled.job });
+        }
+
+        let expected = scheduled.start + request.duration as usize;
+        if scheduled.finish != expected {
+            violations.push(Violation::WrongFinish {
+                job: scheduled.job,
+                expected,
+                found: scheduled.finish,
+            });
+        }
+    }
+
+    let mut jobs: Vec<JobId> = psp
+        .request_durations
+        .iter()
+        .map(|request| request.job_number)
+        .collect();
+    jobs.sort_unstable();
+    jobs.dedup();
+    let listed: HashSet<JobId> = solution
+        .jobs
+        .iter()
+        .map(|scheduled| scheduled.job)
+        .collect();
+    violations.extend(
+        jobs.iter()
+            .filter(|job| !listed.contains(*job))
+            .map(|job| Violation::MissingJob { job: *job }),
+    );
+
+    for relation in &psp.precedence_relations {
+        let Some(&(predecessor_start, request)) = executions.get(&relation.job_number) else {
+            continue;
+        };
+
+        for (index, successor) in relation.successors.iter().enumerate() {
+            let Some(&(successor_start, _)) = executions.get(successor) else {
+                continue;
+            };
+
+            // Without explicit time lags, a successor may start once the job finished
+            let time_lag = relation
+                .time_lags
+                .get(index)
+                .map(|time_lag| *time_lag as i64)
+                .unwrap_or(request.duration as i64);
+            if (successor_start as i64) < predecessor_start as i64 + time_lag {
+                violations.push(Violation::Precedence {
+                    predecessor: relation.job_number,
+                    successor: *successor,
+                    time_lag,
+                    predecessor_start,
+                    successor_start,
+                });
+            }
+        }
+    }
+
+    for (info, project_jobs) in psp.project_jobs() {
+        let release_date = info.relative_date as usize;
+        for job in project_jobs {
+            if let Some(&(start, _)) = executions.get(&job) {
+                if start < release_date {
+                    violations.push(Violation::ReleaseDate {
+                        job,
+                        release_date,
+                        start,
+                    });
+                }
+            }
+        }
+    }
+
+    let actual_makespan = executions
+        .values()
+        .map(|(start, request)| start + request.duration as usize)
+        .max()
+        .unwrap_or(0);
+    let capacities = &psp.resource_availabilities.resources;
+    let capacity = |index: usize| capacities.get(index).copied().unwrap_or(0);
+
+    for index in psp.resources.per_period_indices() {
+        let mut usage = vec![0_u64; actual_makespan];
+        for (start, request) in executions.values() {
+            let demand = request.resources.get(index).copied().unwrap_or(0) as u64;
+            for used in &mut usage[*start..start + request.duration as usize] {
+                *used += demand;
+            }
+        }
+
+        violations.extend(
+            usage
+                .into_iter()
+                .enumerate()
+                .filter(|(_, used)| *used > capacity(index) as u64)
+                .map(|(period, usage)| Violation::ResourceOverload {
+                    resource: index + 1,
+                    period,
+                    usage,
+                    capacity: capacity(index),
+                }),
+        );
+    }
+
+    for index in psp.resources.budget_indices() {
+        let Some(kind) = psp.resources.kind(index) else {
+            continue;
+        };
+
+        let consumption: u64 = executions
+            .values()
+            .map(|(_, request)| request.consumption(index, kind))
+            .sum();
+        if consumption > capacity(index) as u64 {
+            violations.push(Violation::BudgetExceeded {
+                resource: index + 1,
+                consumption,
+                capacity: capacity(index),
+            });
+        }
+    }
+
+    if solution.makespan != actual_makespan {
+        violations.push(Violation::Makespan {
+            claimed: solution.makespan,
+            actual: actual_makespan,
+        });
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use psp_lib_parser::{parse_patterson, parse_psp_lib};
+
+    use super::{verify, Violation};
+    use crate::{
+        solution::Solution,
+        test_fixtures::{
+            j30_network, network, rank_order, EMPTY_INSTANCE, J30_INSTANCE, SINGLE_JOB_INSTANCE,
+        },
+    };
+
+    #[test]
+    fn decoded_solutions_are_valid() {
+        for file in [
+            J30_INSTANCE,
+            include_str!("../../examples/multi_mode.mm"),
+            include_str!("../../examples/multi_project.sm"),
+        ] {
+            let dag = network(parse_psp_lib(file).unwrap());
+            let solution = dag.compute_solution(&rank_order(&dag));
+
+            assert_eq!(verify(&dag.psp, &solution), vec![]);
+        }
+    }
+
+    #[test]
+    fn empty_solutions() {
+        // Even the dummy jobs of a project without any other job have to be scheduled
+        for (instance, jobs) in [(EMPTY_INSTANCE, 2), (SINGLE_JOB_INSTANCE, 3)] {
+            let psp = parse_patterson(instance).unwrap();
+            let violations = verify(&psp, &Solution::new(vec![], vec![]));
+
+            let missing: Vec<_> = (1..=jobs)
+                .map(|job| Violation::MissingJob { job })
+                .collect();
+            assert_eq!(violations, missing);
+        }
+    }
+
+    #[test]
+    fn violations() {
+        let dag = j30_network();
+        let psp = &dag.psp;
+        let solution = dag.compute_solution(&rank_order(&dag));
+
+        // Job 8 succeeds job 2, which lasts 9 periods
+        let mut early = solution.clone();
+        let start = early.get(2).unwrap().start + 8;
+        let job = early.jobs.iter_mut().find(|job| job.job == 8).unwrap();
+        let duration = job.finish - job.start;
+        (job.start, job.finish) = (start, start + duration);
+        assert!(verify(psp, &early).contains(&Violation::Precedence {
+            predecessor: 2,
+            successor: 8,
+            time_lag: 9,
+            predecessor_start: start - 8,
+            successor_start: start,
+        }));
+
+        let mut incomplete = solution.clone();
+        incomplete.jobs.retain(|job| job.job != 5);
+        incomplete.makespan += 1;
+        let violations = verify(psp, &incomplete);
+        assert!(violations.contains(&Violation::MissingJob { job: 5 }));
+        assert!(violations.contains(&Violation::Makespan {
+            claimed: solution.makespan + 1,
+            actual: solution.makespan,
+        }));
+
+        // All jobs at once exceed every resource
+        let mut parallel = solution;
+        for job in &mut parallel.jobs {
+            (job.start, job.finish) = (0, job.finish - job.start);
+        }
+        parallel.jobs[3].mode = 2;
+        let violations = verify(psp, &parallel);
+        assert!(violations.contains(&Violation::UnknownMode { job: 4, mode: 2 }));
+        for resource in 1..=4 {
+            assert!(violations.iter().any(|violation| matches!(
+                violation,
+                Violation::ResourceOverload { resource: found, period: 0, .. } if *found == resource
+            )));
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use anyhow::Result;
-use psp_lib_parser::parse_psp_lib;
+use anyhow::{Context, Result};
+use psp_lib_parser::{decode_solution_list, parse_solution_list, PspLibSolutionKey};
 use rcpsp::scheduler::{custom, rayon, rayon_multi};
 use std::time::Instant;
 
+use super::read_problem;
 use crate::Benchmark;
 
 pub fn benchmark(benchmark: Benchmark) -> Result<()> {
@@ -12,6 +13,13 @@ pub fn benchmark(benchmark: Benchmark) -> Result<()> {
 
     let folder = benchmark.psp_problem_file_folder.read_dir()?;
 
+    let solutions = match &benchmark.solutions {
+        Some(path) => Some(parse_solution_list(&decode_solution_list(&std::fs::read(
+            path,
+        )?))?),
+        None => None,
+    };
+
     let scheduler = match benchmark.algorithm {
         crate::Algorithm::Rayon => rayon::scheduler,
         crate::Algorithm::Custom => custom::scheduler,
@@ -21,8 +29,7 @@ pub fn benchmark(benchmark: Benchmark) -> Result<()> {
     let scheduling_results: Vec<String> = folder
         .map(|path| path.unwrap().path())
         .filter(|path| path.is_file())
-        .map(|path| (path.clone(), std::fs::read_to_string(path).unwrap()))
-        .map(|(path, content)| (path, parse_psp_lib(&content).unwrap()))
+        .map(|path| (path.clone(), read_problem(&path).unwrap()))
         .map(|(path, psp)| {
             (
                 path,
@@ -38,17 +45,31 @@ pub fn benchmark(benchmark: Benchmark) -> Result<()> {
                         iter_since_best_reset: benchmark.iter_since_best_reset,
                         schedule_count: benchmark.number_of_schedules,
                         schedule_duration: benchmark.scheduling_duration,
+                        objective: benchmark.objective.into(),
+                        decoder: benchmark.decoder.into(),
                     },
                 ),
             )
         })
         .map(|(path, start_time, os)| {
-            let os_duration = os.duration;
+            let os_duration = os.with_context(|| path.display().to_string())?.duration;
             let elapsed = start_time.elapsed();
 
-            format!("{path:?}, {os_duration}, {elapsed:?}")
+            match &solutions {
+                Some(solutions) => {
+                    let best_known = PspLibSolutionKey::from_file_name(&path)
+                        .and_then(|key| solutions.get(&key))
+                        .map(|solution| solution.makespan.to_string())
+                        .unwrap_or_default();
+
+                    Ok(format!(
+                        "{path:?}, {os_duration}, {elapsed:?}, {best_known}"
+                    ))
+                }
+                None => Ok(format!("{path:?}, {os_duration}, {elapsed:?}")),
+            }
         })
-        .collect();
+        .collect::<Result<_>>()?;
 
     std::fs::write(benchmark.output, scheduling_results.join("\n"))?;
 
@@ -0,0 +1,20 @@
+use anyhow::Result;
+use log::info;
+use psp_lib_parser::write_psp_lib;
+
+use super::read_problem;
+use crate::{Convert, Format};
+
+pub fn convert(convert: Convert) -> Result<()> {
+    let psp = read_problem(&convert.psp_problem_file)?;
+
+    let contents = match convert.format {
+        Format::Text => write_psp_lib(&psp)?,
+        Format::Json => serde_json::to_string_pretty(&psp)?,
+    };
+    std::fs::write(&convert.output, contents)?;
+
+    info!("Wrote converted instance to: {:?}", convert.output);
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use log::info;
+use psp_lib_parser::write_psp_lib;
+use rcpsp::generator::{self, GeneratorOptions};
+
+use crate::{Format, Generate};
+
+pub fn generate(generate: Generate) -> Result<()> {
+    let psp = generator::generate(&GeneratorOptions {
+        jobs: generate.jobs,
+        resources: generate.resources,
+        network_complexity: generate.network_complexity,
+        resource_factor: generate.resource_factor,
+        resource_strength: generate.resource_strength,
+        seed: generate.seed,
+        ..GeneratorOptions::default()
+    })?;
+
+    let contents = match generate.format {
+        Format::Text => write_psp_lib(&psp)?,
+        Format::Json => serde_json::to_string_pretty(&psp)?,
+    };
+    std::fs::write(&generate.output, contents)?;
+
+    info!("Wrote generated instance to: {:?}", generate.output);
+
+    Ok(())
+}
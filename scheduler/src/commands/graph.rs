@@ -1,14 +1,12 @@
 use anyhow::Result;
-use log::{debug, info, trace};
-use psp_lib_parser::{parse_psp_lib, PspLibRequestDuration};
+use log::{debug, info};
+use psp_lib_parser::{JobId, PspLibRequestDuration};
 use std::{collections::HashMap, fs, io::Write, path::PathBuf};
 
-pub fn graph(input_path: PathBuf, output_path: PathBuf) -> Result<()> {
-    let contents = fs::read_to_string(input_path)?;
-    trace!("input file contents: {contents}");
+use super::read_problem;
 
-    let psp = parse_psp_lib(contents.as_str())?;
-    trace!("parsed psp: {psp:#?}");
+pub fn graph(input_path: PathBuf, output_path: PathBuf) -> Result<()> {
+    let psp = read_problem(&input_path)?;
 
     debug!(
         "Creating graph for file with basedata: {}",
@@ -26,9 +24,10 @@ pub fn graph(input_path: PathBuf, output_path: PathBuf) -> Result<()> {
 fn write_dot_file(mut file: fs::File, psp: psp_lib_parser::structs::PspLibProblem) -> Result<()> {
     // Calculate same execution ranks
     // ranks = dependency ranks
-    let ranks: Vec<Vec<u8>> = rcpsp::dag::DAG::new(psp.clone(), 15).compute_job_execution_ranks();
+    let ranks: Vec<Vec<JobId>> =
+        rcpsp::dag::DAG::new(psp.clone(), 15)?.compute_job_execution_ranks();
 
-    let durations: HashMap<u8, PspLibRequestDuration> = psp
+    let durations: HashMap<JobId, PspLibRequestDuration> = psp
         .request_durations
         .into_iter()
         .map(|a| (a.job_number, a))
@@ -75,7 +74,7 @@ fn write_dot_file(mut file: fs::File, psp: psp_lib_parser::structs::PspLibProble
 
     file.write_all(b"\n")?;
 
-    let mut prerequisite_map: HashMap<u8, Vec<u8>> = HashMap::new();
+    let mut prerequisite_map: HashMap<JobId, Vec<JobId>> = HashMap::new();
 
     for (job_number, successors) in successor_map.iter() {
         for successor in successors {
@@ -1,7 +1,61 @@
+use std::path::Path;
+
+use anyhow::Result;
+use log::{error, trace};
+use psp_lib_parser::{parse_file, read_instance, validate_psp_lib, PspLibProblem};
+
 mod benchmark;
+mod convert;
+mod generate;
 mod graph;
 mod schedule;
+mod stats;
+mod validate;
 
 pub use benchmark::benchmark;
+pub use convert::convert;
+pub use generate::generate;
 pub use graph::graph;
 pub use schedule::schedule;
+pub use stats::stats;
+pub use validate::validate;
+
+/// Reads, parses and validates a problem instance, selecting the format by file extension.
+///
+/// Gzip-compressed instances are inflated transparently, `.json` files are read as JSON.
+pub(crate) fn read_problem(path: &Path) -> Result<PspLibProblem> {
+    let psp = if is_json(path) {
+        serde_json::from_str(&read_instance(path)?)?
+    } else {
+        parse_file(path)?
+    };
+    trace!("parsed psp: {psp:#?}");
+
+    let errors = validate_psp_lib(&psp);
+    if !errors.is_empty() {
+        for err in &errors {
+            error!("{}: {err}", path.display());
+        }
+
+        anyhow::bail!(
+            "{} is not a valid instance, found {} errors",
+            path.display(),
+            errors.len()
+        )
+    }
+
+    Ok(psp)
+}
+
+/// Whether the file holds a JSON instance, ignoring a trailing `.gz`
+fn is_json(path: &Path) -> bool {
+    let file_name = path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .unwrap_or_default();
+
+    file_name
+        .strip_suffix(".gz")
+        .unwrap_or(file_name)
+        .ends_with(".json")
+}
@@ -1,19 +1,31 @@
-use std::fs;
+use std::path::PathBuf;
 
-use anyhow::Result;
-use log::trace;
-use psp_lib_parser::parse_psp_lib;
-use rcpsp::scheduler::{custom, rayon, rayon_multi};
+use anyhow::{Context, Result};
+use log::{debug, error, info};
+use rcpsp::{
+    dag::DAG,
+    portfolio::{average_project_delay, total_weighted_tardiness, ProjectOutcome},
+    scheduler::{custom, rayon, rayon_multi, OptimizedSchedule, SchedulerOptions},
+};
+use serde::Serialize;
 
-use crate::Schedule;
+use super::read_problem;
+use crate::{Format, Schedule};
+
+/// Result of scheduling a single instance, as written by `--format json`
+#[derive(Debug, Serialize)]
+struct ScheduleReport {
+    instance: PathBuf,
+    options: SchedulerOptions,
+    result: OptimizedSchedule,
+    projects: Vec<ProjectOutcome>,
+    total_weighted_tardiness: usize,
+    average_project_delay: f64,
+}
 
 pub fn schedule(schedule: Schedule) -> Result<()> {
     for input_file in schedule.input_files {
-        let contents = fs::read_to_string(input_file)?;
-        trace!("input file contents: {contents}");
-
-        let psp = parse_psp_lib(contents.as_str())?;
-        trace!("parsed psp: {psp:#?}");
+        let psp = read_problem(&input_file)?;
 
         let scheduler = match schedule.algorithm {
             crate::Algorithm::Rayon => rayon::scheduler,
@@ -21,19 +33,89 @@ pub fn schedule(schedule: Schedule) -> Result<()> {
             crate::Algorithm::RayonMulti => rayon_multi::scheduler,
         };
 
-        scheduler(
-            psp,
-            rcpsp::scheduler::SchedulerOptions {
-                number_of_iterations: schedule.number_of_iterations,
-                max_iter_since_best: schedule.max_iter_since_best,
-                tabu_list_size: schedule.tabu_list_size,
-                swap_range: schedule.swap_range,
-                parallel: schedule.parallel,
-                iter_since_best_reset: schedule.iter_since_best_reset,
-                schedule_count: schedule.number_of_schedules,
-                schedule_duration: schedule.scheduling_duration,
-            },
+        let options = SchedulerOptions {
+            number_of_iterations: schedule.number_of_iterations,
+            max_iter_since_best: schedule.max_iter_since_best,
+            tabu_list_size: schedule.tabu_list_size,
+            swap_range: schedule.swap_range,
+            parallel: schedule.parallel,
+            iter_since_best_reset: schedule.iter_since_best_reset,
+            schedule_count: schedule.number_of_schedules,
+            schedule_duration: schedule.scheduling_duration,
+            objective: schedule.objective.into(),
+            decoder: schedule.decoder.into(),
+        };
+        let dag = DAG::new(psp.clone(), options.swap_range)
+            .with_context(|| input_file.display().to_string())?
+            .with_objective(options.objective)
+            .with_decoder(options.decoder);
+        let violations = dag.budget_violations();
+        if !violations.is_empty() {
+            for violation in &violations {
+                error!(
+                    "{}: resource {} consumes {} units of its budget of {}",
+                    input_file.display(),
+                    violation.resource,
+                    violation.consumption,
+                    violation.capacity
+                );
+            }
+
+            anyhow::bail!(
+                "{}: no mode assignment keeping all resource budgets was found",
+                input_file.display()
+            )
+        }
+
+        let result = scheduler(psp, options.clone())?;
+        match result.gap {
+            Some(gap) => info!(
+                "objective {}, lower bound {}, gap {:.2}%",
+                result.duration,
+                result.lower_bound,
+                gap * 100.0
+            ),
+            None => info!(
+                "objective {}, lower bound {}",
+                result.duration, result.lower_bound
+            ),
+        }
+
+        for scheduled in &result.solution.jobs {
+            debug!(
+                "job {}: start {}, finish {}",
+                scheduled.job, scheduled.start, scheduled.finish
+            );
+        }
+
+        let projects = dag.compute_project_outcomes(&result.schedule);
+        for outcome in &projects {
+            info!(
+                "project {}: completion {}, makespan {}, tardiness {}, delay {}",
+                outcome.project,
+                outcome.completion,
+                outcome.makespan,
+                outcome.tardiness,
+                outcome.delay
+            );
+        }
+        info!(
+            "total weighted tardiness: {}, average project delay: {}",
+            total_weighted_tardiness(&projects),
+            average_project_delay(&projects)
         );
+
+        if schedule.format == Format::Json {
+            let report = ScheduleReport {
+                instance: input_file,
+                options,
+                result,
+                total_weighted_tardiness: total_weighted_tardiness(&projects),
+                average_project_delay: average_project_delay(&projects),
+                projects,
+            };
+            println!("{}", serde_json::to_string(&report)?);
+        }
     }
 
     Ok(())
@@ -0,0 +1,84 @@
+use anyhow::Result;
+use log::info;
+use rcpsp::{characteristics::InstanceCharacteristics, dag::DAG};
+
+use super::read_problem;
+use crate::{Stats, StatsFormat};
+
+const COLUMNS: [&str; 10] = [
+    "instance",
+    "jobs",
+    "resources",
+    "nc",
+    "os",
+    "rf",
+    "rs",
+    "dr",
+    "cpl",
+    "lb_res",
+];
+
+pub fn stats(stats: Stats) -> Result<()> {
+    let rows = stats
+        .files
+        .iter()
+        .map(|path| {
+            let characteristics = InstanceCharacteristics::new(&DAG::new(read_problem(path)?, 1)?);
+
+            Ok(vec![
+                path.display().to_string(),
+                characteristics.jobs.to_string(),
+                characteristics.resources.to_string(),
+                format!("{:.3}", characteristics.network_complexity),
+                format!("{:.3}", characteristics.order_strength),
+                format!("{:.3}", characteristics.resource_factor),
+                format!("{:.3}", characteristics.resource_strength),
+                format!("{:.3}", characteristics.disjunction_ratio),
+                characteristics.critical_path_length.to_string(),
+                characteristics.resource_lower_bound.to_string(),
+            ])
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let contents = match stats.format {
+        StatsFormat::Csv => std::iter::once(COLUMNS.join(","))
+            .chain(rows.iter().map(|row| row.join(",")))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        StatsFormat::Table => {
+            let widths: Vec<usize> = COLUMNS
+                .iter()
+                .enumerate()
+                .map(|(column, name)| {
+                    rows.iter()
+                        .map(|row| row[column].len())
+                        .chain([name.len()])
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+
+            std::iter::once(COLUMNS.map(String::from).to_vec())
+                .chain(rows)
+                .map(|row| {
+                    row.iter()
+                        .zip(&widths)
+                        .map(|(cell, width)| format!("{cell:>width$}"))
+                        .collect::<Vec<_>>()
+                        .join("  ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    };
+
+    match &stats.output {
+        Some(output) => {
+            std::fs::write(output, contents + "\n")?;
+            info!("Wrote instance characteristics to: {output:?}");
+        }
+        None => println!("{contents}"),
+    }
+
+    Ok(())
+}
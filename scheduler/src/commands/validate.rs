@@ -0,0 +1,44 @@
+use anyhow::Result;
+use log::{error, info};
+use psp_lib_parser::read_instance;
+use rcpsp::{solution::Solution, verifier::verify};
+
+use super::read_problem;
+use crate::Validate;
+
+pub fn validate(validate: Validate) -> Result<()> {
+    let psp = read_problem(&validate.psp_problem_file)?;
+
+    // Reports written by `schedule --format json` hold the solution in `result.solution`
+    let document: serde_json::Value =
+        serde_json::from_str(&read_instance(&validate.solution_file)?)?;
+    let solution: Solution = serde_json::from_value(
+        document
+            .pointer("/result/solution")
+            .cloned()
+            .unwrap_or(document),
+    )?;
+
+    let violations = verify(&psp, &solution);
+    if !violations.is_empty() {
+        for violation in &violations {
+            error!("{}: {violation}", validate.solution_file.display());
+        }
+
+        anyhow::bail!(
+            "{} is not a valid solution of {}, found {} violations",
+            validate.solution_file.display(),
+            validate.psp_problem_file.display(),
+            violations.len()
+        )
+    }
+
+    info!(
+        "{} is a valid solution of {} with makespan {}",
+        validate.solution_file.display(),
+        validate.psp_problem_file.display(),
+        solution.makespan
+    );
+
+    Ok(())
+}
@@ -25,6 +25,14 @@ pub enum Commands {
     Schedule(Schedule),
     /// Run benchmarks and result evaluations for scheduler
     Benchmark(Benchmark),
+    /// Convert a problem instance between PSPLIB and JSON
+    Convert(Convert),
+    /// Generate a random problem instance
+    Generate(Generate),
+    /// Compute characteristics like network complexity and resource strength of instances
+    Stats(Stats),
+    /// Check a solution against all constraints of its instance
+    Validate(Validate),
 }
 
 #[derive(Debug, Parser)]
@@ -35,6 +43,70 @@ pub struct Graph {
     output: PathBuf,
 }
 
+#[derive(Debug, Parser)]
+pub struct Convert {
+    /// Instance to convert, in any supported input format
+    #[clap(required = true)]
+    psp_problem_file: PathBuf,
+    /// File to write the converted instance to
+    #[clap(required = true)]
+    output: PathBuf,
+    /// Format of the written instance
+    #[clap(value_enum, long, default_value_t = Format::Json)]
+    format: Format,
+}
+
+#[derive(Debug, Parser)]
+pub struct Generate {
+    /// File to write the generated instance to
+    #[clap(required = true)]
+    output: PathBuf,
+    /// Number of non-dummy jobs
+    #[clap(long, default_value_t = 30)]
+    jobs: usize,
+    /// Number of renewable resources
+    #[clap(long, default_value_t = 4)]
+    resources: usize,
+    /// Average number of non-redundant arcs per job
+    #[clap(long, visible_alias = "nc", default_value_t = 1.5)]
+    network_complexity: f64,
+    /// Average fraction of the resources requested by a job, between 0 and 1
+    #[clap(long, visible_alias = "rf", default_value_t = 0.5)]
+    resource_factor: f64,
+    /// Scarcity of the resources, between 0 (tightest) and 1 (no conflicts)
+    #[clap(long, visible_alias = "rs", default_value_t = 0.5)]
+    resource_strength: f64,
+    /// Seed of the random number generator
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+    /// Format of the written instance
+    #[clap(value_enum, long, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Debug, Parser)]
+pub struct Stats {
+    /// Instances to characterize, in any supported input format
+    #[clap(required = true, num_args = 1..)]
+    files: Vec<PathBuf>,
+    /// Layout of the written characteristics
+    #[clap(value_enum, long, default_value_t = StatsFormat::default())]
+    format: StatsFormat,
+    /// File to write the characteristics to instead of stdout
+    #[clap(long, short = 'o')]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+pub struct Validate {
+    /// Instance the solution belongs to, in any supported input format
+    #[clap(required = true)]
+    psp_problem_file: PathBuf,
+    /// Solution as JSON, either on its own or within a report of `schedule --format json`
+    #[clap(required = true)]
+    solution_file: PathBuf,
+}
+
 #[derive(Debug, Parser)]
 pub struct Benchmark {
     /// Folder location containing a collection of PSP tasks
@@ -73,6 +145,15 @@ pub struct Benchmark {
     /// Amount of seconds after which to terminate the scheduling
     #[clap(long, visible_alias = "time")]
     scheduling_duration: Option<u64>,
+    /// PSPLIB solution list (e.g. j30opt.sm or j120hrs.sm) to compare results against
+    #[clap(long)]
+    solutions: Option<PathBuf>,
+    /// Value to minimize
+    #[clap(value_enum, long, default_value_t = Objective::default())]
+    objective: Objective,
+    /// Schedule generation scheme turning activity lists into schedules
+    #[clap(value_enum, long, default_value_t = Decoder::default())]
+    decoder: Decoder,
 }
 
 #[derive(Debug, Parser)]
@@ -119,6 +200,77 @@ pub struct Schedule {
     /// Amount of seconds after which to terminate the scheduling
     #[clap(long, visible_alias = "time")]
     scheduling_duration: Option<u64>,
+
+    /// Value to minimize
+    #[clap(value_enum, long, default_value_t = Objective::default())]
+    objective: Objective,
+    /// Schedule generation scheme turning activity lists into schedules
+    #[clap(value_enum, long, default_value_t = Decoder::default())]
+    decoder: Decoder,
+
+    /// Output format of the resulting schedules
+    #[clap(value_enum, long, default_value_t = Format::default())]
+    format: Format,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum, Default)]
+pub enum Objective {
+    /// Time until all jobs are finished
+    #[default]
+    Makespan,
+    /// Sum of the project tardinesses weighted by their tardiness costs
+    TotalWeightedTardiness,
+    /// Sum of the delays of the projects beyond their critical path length
+    TotalProjectDelay,
+}
+
+impl From<Objective> for rcpsp::portfolio::Objective {
+    fn from(objective: Objective) -> Self {
+        match objective {
+            Objective::Makespan => Self::Makespan,
+            Objective::TotalWeightedTardiness => Self::TotalWeightedTardiness,
+            Objective::TotalProjectDelay => Self::TotalProjectDelay,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum, Default)]
+pub enum Decoder {
+    /// Serial schedule generation scheme
+    #[default]
+    Serial,
+    /// Parallel (time incrementing) schedule generation scheme
+    Parallel,
+    /// Better result of the serial and the parallel scheme
+    BestOf,
+}
+
+impl From<Decoder> for rcpsp::dag::Decoder {
+    fn from(decoder: Decoder) -> Self {
+        match decoder {
+            Decoder::Serial => Self::Serial,
+            Decoder::Parallel => Self::Parallel,
+            Decoder::BestOf => Self::BestOf,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum, Default)]
+pub enum Format {
+    /// PSPLIB text for instances, log output for schedules
+    #[default]
+    Text,
+    /// JSON documents as described in the README
+    Json,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum, Default)]
+pub enum StatsFormat {
+    /// Aligned columns for reading in a terminal
+    #[default]
+    Table,
+    /// Comma separated values with a header row
+    Csv,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
@@ -157,7 +309,13 @@ fn main() {
         }) => commands::graph(psp_problem_file, output),
         Commands::Schedule(schedule) => commands::schedule(schedule),
         Commands::Benchmark(benchmark) => commands::benchmark(benchmark),
+        Commands::Convert(convert) => commands::convert(convert),
+        Commands::Generate(generate) => commands::generate(generate),
+        Commands::Stats(stats) => commands::stats(stats),
+        Commands::Validate(validate) => commands::validate(validate),
     } {
         error!("An error occurred: {}", err);
+        // Lets scripts tell failed commands, e.g. invalid solutions, from successful ones
+        std::process::exit(1);
     }
 }